@@ -0,0 +1,38 @@
+use std::{hint::black_box, sync::Arc, thread};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use stores::{AtomicObservable, Observable, Readable};
+
+const THREADS: usize = 4;
+const READS: usize = 1_000;
+
+/// Reads the store from several threads at once.
+fn read_concurrently(store: &Arc<impl Readable<usize> + Send + Sync + 'static>) {
+    let threads: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let store = store.clone();
+            thread::spawn(move || {
+                for _ in 0..READS {
+                    black_box(store.get());
+                }
+            })
+        })
+        .collect();
+
+    threads
+        .into_iter()
+        .for_each(|thread| thread.join().unwrap());
+}
+
+fn concurrent_reads(c: &mut Criterion) {
+    let observable = Observable::new(0);
+    let atomic = AtomicObservable::new(0);
+
+    let mut group = c.benchmark_group("concurrent_reads");
+    group.bench_function("rwlock", |b| b.iter(|| read_concurrently(&observable)));
+    group.bench_function("arc_swap", |b| b.iter(|| read_concurrently(&atomic)));
+    group.finish();
+}
+
+criterion_group!(benches, concurrent_reads);
+criterion_main!(benches);
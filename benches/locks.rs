@@ -0,0 +1,64 @@
+use std::{hint::black_box, sync::Arc, thread};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use stores::{Observable, Readable, Writable};
+
+const THREADS: usize = 4;
+const OPERATIONS: usize = 1_000;
+
+/// Name of the lock backend the benchmarks are compiled with.
+fn backend() -> &'static str {
+    if cfg!(feature = "parking_lot") {
+        "parking_lot"
+    } else if cfg!(feature = "spin") {
+        "spin"
+    } else {
+        "std"
+    }
+}
+
+/// Runs `reads` and `writes` operations on the observable from several threads at once.
+fn workload(observable: &Arc<Observable<usize>>, reads: usize, writes: usize) {
+    let threads: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let observable = observable.clone();
+            thread::spawn(move || {
+                for _ in 0..reads {
+                    black_box(observable.get());
+                }
+                for value in 0..writes {
+                    observable.set(value);
+                }
+            })
+        })
+        .collect();
+
+    threads
+        .into_iter()
+        .for_each(|thread| thread.join().unwrap());
+}
+
+fn read_heavy(c: &mut Criterion) {
+    let observable = Observable::new(0);
+    let _unsubscribe = observable.subscribe(|value| {
+        black_box(value);
+    });
+
+    c.bench_function(&format!("read_heavy/{}", backend()), |b| {
+        b.iter(|| workload(&observable, OPERATIONS, OPERATIONS / 100))
+    });
+}
+
+fn write_heavy(c: &mut Criterion) {
+    let observable = Observable::new(0);
+    let _unsubscribe = observable.subscribe(|value| {
+        black_box(value);
+    });
+
+    c.bench_function(&format!("write_heavy/{}", backend()), |b| {
+        b.iter(|| workload(&observable, OPERATIONS / 100, OPERATIONS))
+    });
+}
+
+criterion_group!(benches, read_heavy, write_heavy);
+criterion_main!(benches);
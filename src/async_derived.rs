@@ -0,0 +1,301 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    fmt::Debug,
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use tokio::{runtime::Handle, task::JoinHandle};
+
+use crate::{Emitter, Loadable, ReadRef, Readable, Subscribers, Unsubscribe, lock::RwLock};
+
+type Compute<Value> = Box<dyn Fn() -> Pin<Box<dyn Future<Output = Value> + Send>> + Send + Sync>;
+
+/// A readable observable value that is derived from other observables by an async computation.
+///
+/// Every change of a target spawns the computation on the tokio runtime and switches the value to
+/// `Loading` until it resolves. A computation that is still running when a target changes again
+/// is cancelled, so stale results are never stored.
+pub struct AsyncDerived<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    value: RwLock<Loadable<Value>>,
+    compute: Compute<Value>,
+    runtime: Handle,
+    generation: AtomicUsize,
+    task: RwLock<Option<JoinHandle<()>>>,
+    subscribers: Subscribers<Loadable<Value>>,
+}
+
+impl<Value> AsyncDerived<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    /// Creates a new asynchronously derived value.
+    ///
+    /// The computation is spawned immediately and whenever one of the targets changes.
+    /// It has to be called from within a tokio runtime, which is used for all computations.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{AsyncDerived, Loadable, Observable, Readable};
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let id = Observable::new(1);
+    /// let user = AsyncDerived::new(&[id.clone()], {
+    ///     let id = id.clone();
+    ///     move || {
+    ///         let id = id.get();
+    ///         async move { format!("user {id}") }
+    ///     }
+    /// });
+    /// assert_eq!(user.get(), Loadable::Loading);
+    /// # }
+    /// ```
+    pub fn new<Fut>(
+        targets: &[Arc<dyn Emitter + Send + Sync>],
+        compute: impl Fn() -> Fut + Send + Sync + 'static,
+    ) -> Arc<Self>
+    where
+        Fut: Future<Output = Value> + Send + 'static,
+    {
+        let instance = Arc::new(Self {
+            value: RwLock::new(Loadable::Loading),
+            compute: Box::new(move || Box::pin(compute())),
+            runtime: Handle::current(),
+            generation: AtomicUsize::new(0),
+            task: RwLock::new(None),
+            subscribers: Subscribers::new(),
+        });
+        instance.spawn(0);
+
+        for target in targets {
+            let unsubscribe = target.listen_boxed(Box::new({
+                let instance = Arc::downgrade(&instance);
+                move || {
+                    if let Some(instance) = instance.upgrade() {
+                        instance.refresh();
+                    }
+                }
+            }));
+            instance.subscribers.hold(unsubscribe);
+        }
+
+        instance
+    }
+
+    /// Names the store, so its changes are reported to the global hook.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::AsyncDerived;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let derived = AsyncDerived::new(&[], || async { 1 });
+    /// derived.name("derived");
+    /// # }
+    /// ```
+    #[cfg(feature = "debug")]
+    pub fn name(&self, name: &str)
+    where
+        Value: Debug,
+    {
+        self.subscribers.name(name);
+    }
+
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::AsyncDerived;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let derived = AsyncDerived::new(&[], || async { 1 });
+    /// assert_eq!(derived.subscriber_count(), 0);
+    /// # }
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count()
+    }
+
+    /// Internal function to switch to loading and spawn a new computation.
+    fn refresh(self: &Arc<Self>) {
+        let generation = {
+            let mut value = self.value.write();
+            *value = Loadable::Loading;
+            self.generation.fetch_add(1, Ordering::AcqRel) + 1
+        };
+
+        self.notify();
+        self.spawn(generation);
+    }
+
+    /// Internal function to spawn the computation, cancelling the previous one.
+    fn spawn(self: &Arc<Self>, generation: usize) {
+        let future = (self.compute)();
+        let instance = Arc::downgrade(self);
+        let task = self.runtime.spawn(async move {
+            let value = future.await;
+            if let Some(instance) = instance.upgrade() {
+                instance.resolve(generation, value);
+            }
+        });
+
+        if let Some(previous) = self.task.write().replace(task) {
+            previous.abort();
+        }
+    }
+
+    /// Internal function to store a computed value, unless a newer computation was spawned.
+    fn resolve(&self, generation: usize, value: Value) {
+        {
+            let mut current = self.value.write();
+            if self.generation.load(Ordering::Acquire) != generation {
+                return;
+            }
+
+            *current = Loadable::Ready(value);
+        }
+
+        self.notify();
+    }
+
+    /// Internal function to run all registered callbacks.
+    fn notify(&self) {
+        let value = self.value.read().clone();
+        self.subscribers.notify(&value);
+    }
+}
+
+impl<Value> Emitter for AsyncDerived<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.subscribers.listen(callback)
+    }
+}
+
+impl<Value> Readable<Loadable<Value>> for AsyncDerived<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn get(&self) -> Loadable<Value> {
+        self.value.read().clone()
+    }
+
+    fn subscribe_boxed(
+        &self,
+        callback: Box<dyn Fn(&Loadable<Value>) + Send + Sync>,
+    ) -> Unsubscribe {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe(value, callback)
+    }
+}
+
+impl<Value> ReadRef<Loadable<Value>> for AsyncDerived<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    fn with<R>(&self, reader: impl FnOnce(&Loadable<Value>) -> R) -> R {
+        reader(&self.value.read())
+    }
+}
+
+impl<Value> Drop for AsyncDerived<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    fn drop(&mut self) {
+        if let Some(task) = self.task.write().take() {
+            task.abort();
+        }
+    }
+}
+
+impl<Value> Debug for AsyncDerived<Value>
+where
+    Value: Debug + Clone + Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AsyncDerived")
+            .field("value", &self.value.read())
+            .field("callbacks", &self.subscribers)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::Notify;
+
+    use crate::{Observable, Writable, as_emitter};
+
+    use super::*;
+
+    /// Yields to the runtime until the value is ready.
+    async fn ready<Value>(derived: &AsyncDerived<Value>) -> Value
+    where
+        Value: Clone + Send + Sync + 'static,
+    {
+        loop {
+            if let Loadable::Ready(value) = derived.get() {
+                return value;
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_derives_asynchronously() {
+        let source = Observable::new(1);
+        let doubled = AsyncDerived::new(&[as_emitter(&source)], {
+            let source = source.clone();
+            move || {
+                let value = source.get();
+                async move { value * 2 }
+            }
+        });
+
+        assert!(doubled.get().is_loading());
+        assert_eq!(ready(&doubled).await, 2);
+
+        source.set(2);
+        assert!(doubled.get().is_loading());
+        assert_eq!(ready(&doubled).await, 4);
+    }
+
+    #[tokio::test]
+    async fn it_cancels_stale_computations() {
+        let source = Observable::new(1);
+        let gate = Arc::new(Notify::new());
+        let derived = AsyncDerived::new(&[as_emitter(&source)], {
+            let source = source.clone();
+            let gate = gate.clone();
+            move || {
+                let value = source.get();
+                let gate = gate.clone();
+                async move {
+                    // The first computation only finishes once the gate is opened.
+                    if value == 1 {
+                        gate.notified().await;
+                    }
+                    value
+                }
+            }
+        });
+        tokio::task::yield_now().await;
+
+        source.set(2);
+        assert_eq!(ready(&derived).await, 2);
+
+        gate.notify_waiters();
+        tokio::task::yield_now().await;
+        assert_eq!(derived.get(), Loadable::Ready(2));
+    }
+}
@@ -0,0 +1,213 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::fmt::Debug;
+
+use arc_swap::ArcSwap;
+
+use crate::{Emitter, ReadRef, Readable, Subscribers, Unsubscribe, Writable};
+
+/// A readable and writable observable value that can be read without locking.
+///
+/// The value is kept behind an `ArcSwap`, so reads never wait for writers or other readers,
+/// which pays off for values that are read a lot but written rarely.
+/// Writes replace the whole `Arc` of the value, callbacks are still registered under a lock.
+pub struct AtomicObservable<Value>
+where
+    Value: Send + Sync,
+{
+    value: ArcSwap<Value>,
+    subscribers: Subscribers<Value>,
+}
+
+impl<Value> AtomicObservable<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    /// Creates a new lock-free observable value.
+    ///
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::AtomicObservable;
+    /// let observable = AtomicObservable::new(1);
+    /// ```
+    pub fn new(value: Value) -> Arc<Self> {
+        Arc::new(Self {
+            value: ArcSwap::from_pointee(value),
+            subscribers: Subscribers::new(),
+        })
+    }
+
+    /// Returns the current value without cloning it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::AtomicObservable;
+    /// let observable = AtomicObservable::new(String::from("Hello"));
+    /// assert_eq!(observable.load().as_str(), "Hello");
+    /// ```
+    pub fn load(&self) -> Arc<Value> {
+        self.value.load_full()
+    }
+
+    /// Replaces the value with an already shared one.
+    ///
+    /// Calling this will trigger all registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use stores::{AtomicObservable, Readable};
+    /// let observable = AtomicObservable::new(1);
+    /// observable.store(Arc::new(2));
+    /// assert_eq!(observable.get(), 2);
+    /// ```
+    pub fn store(&self, value: Arc<Value>) {
+        self.value.store(value.clone());
+        self.notify(&value);
+    }
+
+    /// Names the store, so its changes are reported to the global hook.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::AtomicObservable;
+    /// let observable = AtomicObservable::new(1);
+    /// observable.name("observable");
+    /// ```
+    #[cfg(feature = "debug")]
+    pub fn name(&self, name: &str)
+    where
+        Value: Debug,
+    {
+        self.subscribers.name(name);
+    }
+
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::AtomicObservable;
+    /// let observable = AtomicObservable::new(1);
+    /// assert_eq!(observable.subscriber_count(), 0);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count()
+    }
+
+    /// Internal function to run all registered callbacks.
+    fn notify(&self, value: &Value) {
+        self.subscribers.notify(value);
+    }
+}
+
+impl<Value> Emitter for AtomicObservable<Value>
+where
+    Value: Send + Sync + 'static,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.subscribers.listen(callback)
+    }
+}
+
+impl<Value> Readable<Value> for AtomicObservable<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn get(&self) -> Value {
+        Value::clone(&self.value.load())
+    }
+
+    fn subscribe_boxed(&self, callback: Box<dyn Fn(&Value) + Send + Sync>) -> Unsubscribe {
+        self.subscribers.subscribe(self.get(), callback)
+    }
+}
+
+impl<Value> Writable<Value> for AtomicObservable<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn set(&self, value: Value) {
+        self.store(Arc::new(value));
+    }
+
+    fn update_boxed(&self, updater: Box<dyn Fn(&Value) -> Value + Send + Sync>) {
+        // The updater is retried on concurrent writes, just like for `Observable`.
+        self.value.rcu(|value| updater(value));
+        self.notify(&self.value.load());
+    }
+}
+
+impl<Value> ReadRef<Value> for AtomicObservable<Value>
+where
+    Value: Send + Sync,
+{
+    fn with<R>(&self, reader: impl FnOnce(&Value) -> R) -> R {
+        reader(&self.value.load())
+    }
+}
+
+impl<Value> Debug for AtomicObservable<Value>
+where
+    Value: Debug + Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AtomicObservable")
+            .field("value", &self.value.load())
+            .field("callbacks", &self.subscribers)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    use super::*;
+
+    #[test]
+    fn it_notifies_on_writes() {
+        let observable = AtomicObservable::new(1);
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = observable.subscribe({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+
+        observable.set(2);
+        observable.update(|value| value * 10);
+        observable.store(Arc::new(5));
+
+        assert_eq!(observable.get(), 5);
+        assert_eq!(*values.lock().unwrap(), vec![1, 2, 20, 5]);
+    }
+
+    #[test]
+    fn it_does_not_lose_concurrent_updates() {
+        let observable = AtomicObservable::new(0);
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let observable = observable.clone();
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        observable.update(|value| value + 1);
+                    }
+                })
+            })
+            .collect();
+
+        threads
+            .into_iter()
+            .for_each(|thread| thread.join().unwrap());
+        assert_eq!(observable.get(), 400);
+    }
+}
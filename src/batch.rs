@@ -0,0 +1,127 @@
+use std::{boxed::Box, cell::RefCell, vec::Vec};
+
+type Job = Box<dyn FnOnce()>;
+
+thread_local! {
+    /// Jobs deferred until the end of the current batch, `None` if no batch is running.
+    static QUEUE: RefCell<Option<Vec<Job>>> = const { RefCell::new(None) };
+}
+
+/// Runs the given function as a batch.
+///
+/// Derived values that depend on stores changed inside the batch are recomputed only once,
+/// after the function returns, instead of once per change.
+/// Other callbacks are still run immediately.
+/// Batches are tracked per thread and can be nested, in which case the outermost batch decides
+/// when derived values are recomputed.
+///
+/// # Example
+///
+/// ```
+/// use stores::{Derived, Observable, Readable, Writable, batch};
+/// let a = Observable::new(1);
+/// let b = Observable::new(2);
+/// let sum = Derived::new(&[a.clone(), b.clone()], {
+///     let a = a.clone();
+///     let b = b.clone();
+///     move || a.get() + b.get()
+/// });
+///
+/// batch(|| {
+///     a.set(3);
+///     b.set(4);
+/// }); // sum is only recomputed once
+/// assert_eq!(sum.get(), 7);
+/// ```
+pub fn batch<R>(func: impl FnOnce() -> R) -> R {
+    let outermost = QUEUE.with_borrow_mut(|queue| {
+        if queue.is_some() {
+            return false;
+        }
+
+        *queue = Some(Vec::new());
+        true
+    });
+
+    if !outermost {
+        return func();
+    }
+
+    let _guard = Guard;
+    let result = func();
+    loop {
+        let jobs = QUEUE.with_borrow_mut(|queue| queue.as_mut().map(core::mem::take));
+        match jobs {
+            Some(jobs) if !jobs.is_empty() => jobs.into_iter().for_each(|job| job()),
+            _ => break,
+        }
+    }
+
+    result
+}
+
+/// Internal guard that ends the batch, even if the batched function panics.
+struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        QUEUE.with_borrow_mut(|queue| *queue = None);
+    }
+}
+
+/// Internal function to check whether a batch is running on the current thread.
+pub(crate) fn is_batching() -> bool {
+    QUEUE.with_borrow(Option::is_some)
+}
+
+/// Internal function to defer a job until the end of the current batch.
+///
+/// Runs the job immediately if no batch is running.
+pub(crate) fn defer(job: impl FnOnce() + 'static) {
+    let job = QUEUE.with_borrow_mut(|queue| match queue {
+        Some(queue) => {
+            queue.push(Box::new(job));
+            None
+        }
+        None => Some(job),
+    });
+
+    if let Some(job) = job {
+        job();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        panic,
+        sync::{Arc, Mutex},
+    };
+
+    use super::*;
+
+    #[test]
+    fn it_defers_jobs_until_outermost_batch_ends() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        batch(|| {
+            defer({
+                let events = events.clone();
+                move || events.lock().unwrap().push("deferred")
+            });
+            batch(|| events.lock().unwrap().push("inner"));
+            events.lock().unwrap().push("outer");
+        });
+
+        assert_eq!(*events.lock().unwrap(), vec!["inner", "outer", "deferred"]);
+        assert!(!is_batching());
+    }
+
+    #[test]
+    fn it_ends_batch_on_panic() {
+        let result = panic::catch_unwind(|| batch(|| panic!("failed")));
+
+        assert!(result.is_err());
+        assert!(!is_batching());
+    }
+}
@@ -0,0 +1,220 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::fmt::Debug;
+
+use crate::{
+    Emitter, Observable, ReadRef, Readable, Subscribers, Unsubscribe, Writable, lock::RwLock,
+};
+
+type Backward<In, Out> = Box<dyn Fn(&Out) -> In + Send + Sync>;
+
+/// A writable observable value that maps the value of another observable in both directions.
+///
+/// Reading maps the source value forward, writing maps the new value backward into the source.
+pub struct BiDerived<In, Out>
+where
+    In: Clone + Send + Sync,
+    Out: Clone + Send + Sync,
+{
+    source: Arc<Observable<In>>,
+    backward: Backward<In, Out>,
+    value: RwLock<Out>,
+    subscribers: Subscribers<Out>,
+}
+
+impl<In, Out> BiDerived<In, Out>
+where
+    In: Clone + Send + Sync + 'static,
+    Out: Clone + Send + Sync + 'static,
+{
+    /// Creates a new bidirectionally derived value.
+    ///
+    /// The forward function is run whenever the source value changes.
+    /// The backward function is run whenever a new value is written,
+    /// and its result is written to the source.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{BiDerived, Observable, Readable, Writable};
+    /// let celsius = Observable::new(0.0);
+    /// let fahrenheit = BiDerived::new(
+    ///     &celsius,
+    ///     |celsius| celsius * 9.0 / 5.0 + 32.0,
+    ///     |fahrenheit| (fahrenheit - 32.0) * 5.0 / 9.0,
+    /// );
+    /// assert_eq!(fahrenheit.get(), 32.0);
+    ///
+    /// fahrenheit.set(212.0);
+    /// assert_eq!(celsius.get(), 100.0);
+    /// ```
+    pub fn new(
+        source: &Arc<Observable<In>>,
+        forward: impl Fn(&In) -> Out + Send + Sync + 'static,
+        backward: impl Fn(&Out) -> In + Send + Sync + 'static,
+    ) -> Arc<Self> {
+        let instance = Arc::new(Self {
+            source: source.clone(),
+            backward: Box::new(backward),
+            value: RwLock::new(forward(&source.get())),
+            subscribers: Subscribers::new(),
+        });
+
+        // The instance keeps the source alive, so the source may only reference it weakly.
+        let unsubscribe = source.subscribe_weak(&instance, move |instance, value| {
+            *instance.value.write() = forward(value);
+            instance.notify();
+        });
+        instance.subscribers.hold(unsubscribe);
+
+        instance
+    }
+
+    /// Names the store, so its changes are reported to the global hook.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{BiDerived, Observable};
+    /// let store = BiDerived::new(&Observable::new(1), |value| value * 2, |value| value / 2);
+    /// store.name("bi_derived");
+    /// ```
+    #[cfg(feature = "debug")]
+    pub fn name(&self, name: &str)
+    where
+        Out: Debug,
+    {
+        self.subscribers.name(name);
+    }
+
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{BiDerived, Observable};
+    /// let store = BiDerived::new(&Observable::new(1), |value| value * 2, |value| value / 2);
+    /// assert_eq!(store.subscriber_count(), 0);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count()
+    }
+
+    /// Internal function to run all registered callbacks.
+    fn notify(&self) {
+        let value = self.value.read().clone();
+        self.subscribers.notify(&value);
+    }
+}
+
+impl<In, Out> Emitter for BiDerived<In, Out>
+where
+    In: Clone + Send + Sync + 'static,
+    Out: Clone + Send + Sync + 'static,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.subscribers.listen(callback)
+    }
+}
+
+impl<In, Out> Readable<Out> for BiDerived<In, Out>
+where
+    In: Clone + Send + Sync + 'static,
+    Out: Clone + Send + Sync + 'static,
+{
+    fn get(&self) -> Out {
+        self.value.read().clone()
+    }
+
+    fn subscribe_boxed(&self, callback: Box<dyn Fn(&Out) + Send + Sync>) -> Unsubscribe {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe(value, callback)
+    }
+}
+
+impl<In, Out> Writable<Out> for BiDerived<In, Out>
+where
+    In: Clone + Send + Sync + 'static,
+    Out: Clone + Send + Sync + 'static,
+{
+    fn set(&self, value: Out) {
+        self.source.set((self.backward)(&value));
+    }
+
+    fn update_boxed(&self, updater: Box<dyn Fn(&Out) -> Out + Send + Sync>) {
+        let value = self.value.read().clone();
+        self.set(updater(&value));
+    }
+}
+
+impl<In, Out> ReadRef<Out> for BiDerived<In, Out>
+where
+    In: Clone + Send + Sync,
+    Out: Clone + Send + Sync,
+{
+    fn with<R>(&self, reader: impl FnOnce(&Out) -> R) -> R {
+        reader(&self.value.read())
+    }
+}
+
+impl<In, Out> Debug for BiDerived<In, Out>
+where
+    In: Clone + Send + Sync,
+    Out: Debug + Clone + Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BiDerived")
+            .field("value", &self.value.read())
+            .field("callbacks", &self.subscribers)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    fn fahrenheit(celsius: &Arc<Observable<f64>>) -> Arc<BiDerived<f64, f64>> {
+        BiDerived::new(
+            celsius,
+            |celsius| celsius * 9.0 / 5.0 + 32.0,
+            |fahrenheit| (fahrenheit - 32.0) * 5.0 / 9.0,
+        )
+    }
+
+    #[test]
+    fn it_converts_both_ways() {
+        let celsius = Observable::new(0.0);
+        let fahrenheit = fahrenheit(&celsius);
+        assert_eq!(fahrenheit.get(), 32.0);
+
+        celsius.set(100.0);
+        assert_eq!(fahrenheit.get(), 212.0);
+
+        fahrenheit.set(-40.0);
+        assert_eq!(celsius.get(), -40.0);
+        assert_eq!(fahrenheit.get(), -40.0);
+
+        fahrenheit.update(|value| value + 18.0);
+        assert_eq!(celsius.get(), -30.0);
+        assert_eq!(fahrenheit.get(), -22.0);
+    }
+
+    #[test]
+    fn it_notifies_on_writes() {
+        let celsius = Observable::new(0.0);
+        let fahrenheit = fahrenheit(&celsius);
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = fahrenheit.on_change({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+
+        fahrenheit.set(212.0);
+        celsius.set(0.0);
+        assert_eq!(*values.lock().unwrap(), vec![212.0, 32.0]);
+    }
+}
@@ -0,0 +1,130 @@
+use alloc::sync::Arc;
+use core::{
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+/// Compares and hashes a store by identity instead of by value.
+///
+/// Two handles are equal if they point to the same store, no matter the values they hold.
+/// Allows using stores as keys of a `HashMap` or in a `HashSet`.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashSet;
+/// use stores::{ById, Observable};
+/// let observable = Observable::new(1);
+/// let mut stores = HashSet::new();
+///
+/// stores.insert(ById(observable.clone()));
+/// assert!(stores.contains(&ById(observable)));
+/// assert!(!stores.contains(&ById(Observable::new(1))));
+/// ```
+pub struct ById<Store>(pub Arc<Store>)
+where
+    Store: ?Sized;
+
+impl<Store> ById<Store>
+where
+    Store: ?Sized,
+{
+    /// Internal function to get the address of the store, without any metadata.
+    fn address(&self) -> *const () {
+        Arc::as_ptr(&self.0) as *const ()
+    }
+}
+
+impl<Store> PartialEq for ById<Store>
+where
+    Store: ?Sized,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.address() == other.address()
+    }
+}
+
+impl<Store> Eq for ById<Store> where Store: ?Sized {}
+
+impl<Store> Hash for ById<Store>
+where
+    Store: ?Sized,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.address().hash(state);
+    }
+}
+
+impl<Store> Clone for ById<Store>
+where
+    Store: ?Sized,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<Store> Deref for ById<Store>
+where
+    Store: ?Sized,
+{
+    type Target = Arc<Store>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<Store> From<Arc<Store>> for ById<Store>
+where
+    Store: ?Sized,
+{
+    fn from(store: Arc<Store>) -> Self {
+        Self(store)
+    }
+}
+
+impl<Store> Debug for ById<Store>
+where
+    Store: Debug + ?Sized,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ById").field(&self.0).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{Emitter, Observable, Writable};
+
+    use super::*;
+
+    #[test]
+    // Only the address is hashed, so the interior mutability of the store does not matter.
+    #[allow(clippy::mutable_key_type)]
+    fn it_compares_by_identity() {
+        let a = Observable::new(1);
+        let b = Observable::new(1);
+        let mut stores = HashSet::new();
+
+        assert!(stores.insert(ById(a.clone())));
+        assert!(!stores.insert(ById(a.clone())));
+        assert!(stores.insert(ById(b.clone())));
+
+        a.set(2);
+        assert!(stores.contains(&ById(a)));
+        assert_eq!(stores.len(), 2);
+    }
+
+    #[test]
+    fn it_compares_trait_objects() {
+        let observable = Observable::new(1);
+        let emitter: Arc<dyn Emitter + Send + Sync> = observable.clone();
+
+        assert!(ById(emitter.clone()) == ById(emitter));
+        assert_ne!(ById(observable), ById(Observable::new(1)));
+    }
+}
@@ -0,0 +1,129 @@
+use std::{
+    boxed::Box,
+    collections::VecDeque,
+    sync::{Arc, Condvar, Mutex},
+    time::Instant,
+};
+
+use crate::{Readable, Unsubscribe};
+
+type State<Value> = (Mutex<VecDeque<Value>>, Condvar);
+type Push<Value> = Box<dyn Fn(&Value) + Send + Sync>;
+
+/// A blocking iterator over the changes of a readable.
+///
+/// Created by `Readable::changes`.
+/// Never returns `None`, but blocks until the next value is emitted.
+/// Dropping the iterator removes its subscription.
+pub struct Changes<Value> {
+    state: Arc<State<Value>>,
+    unsubscribe: Unsubscribe,
+}
+
+impl<Value> Changes<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    /// Internal function to subscribe to all changes of the readable.
+    pub(crate) fn new(readable: &impl Readable<Value>) -> Self {
+        Self::queue(|push| readable.on_change(push))
+    }
+
+    /// Internal function to queue every value that is pushed through the given subscription.
+    pub(crate) fn queue(subscribe: impl FnOnce(Push<Value>) -> Unsubscribe) -> Self {
+        let state: Arc<State<Value>> = Arc::new((Mutex::new(VecDeque::new()), Condvar::new()));
+        let unsubscribe = subscribe(Box::new({
+            let state = state.clone();
+            move |value| {
+                state.0.lock().unwrap().push_back(value.clone());
+                state.1.notify_all();
+            }
+        }));
+
+        Self { state, unsubscribe }
+    }
+
+    /// Internal function to block until the next value, or until the deadline has passed.
+    ///
+    /// Returns `None` if no value was emitted before the deadline.
+    pub(crate) fn next_before(&mut self, deadline: Option<Instant>) -> Option<Value> {
+        let (queue, condvar) = &*self.state;
+        let mut queue = queue.lock().unwrap();
+        loop {
+            if let Some(value) = queue.pop_front() {
+                return Some(value);
+            }
+
+            match deadline {
+                None => queue = condvar.wait(queue).unwrap(),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return None;
+                    }
+                    queue = condvar.wait_timeout(queue, deadline - now).unwrap().0;
+                }
+            }
+        }
+    }
+}
+
+impl<Value> Iterator for Changes<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        self.next_before(None)
+    }
+}
+
+impl<Value> Drop for Changes<Value> {
+    fn drop(&mut self) {
+        (self.unsubscribe)();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Barrier, thread};
+
+    use crate::{Observable, Writable};
+
+    use super::*;
+
+    #[test]
+    fn it_yields_changes_across_threads() {
+        let observable = Observable::new(0);
+        let barrier = Arc::new(Barrier::new(2));
+
+        let worker = thread::spawn({
+            let observable = observable.clone();
+            let barrier = barrier.clone();
+            move || {
+                let changes = observable.changes();
+                barrier.wait();
+                changes.take(3).collect::<Vec<_>>()
+            }
+        });
+
+        barrier.wait();
+        observable.set(1);
+        observable.set(2);
+        observable.set(3);
+
+        assert_eq!(worker.join().unwrap(), vec![1, 2, 3]);
+        assert_eq!(observable.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_unsubscribes_on_drop() {
+        let observable = Observable::new(0);
+        let changes = observable.changes();
+        assert_eq!(observable.subscriber_count(), 1);
+
+        drop(changes);
+        assert_eq!(observable.subscriber_count(), 0);
+    }
+}
@@ -1,10 +1,17 @@
-use std::{
-    collections::HashMap,
+use alloc::{boxed::Box, sync::Arc};
+use core::{
     fmt::Debug,
-    sync::{Arc, RwLock},
+    sync::atomic::{AtomicU64, Ordering},
 };
+#[cfg(feature = "std")]
+use std::hash::Hash;
 
-use crate::{Callback, Emitter, Observable, Readable, Writable};
+#[cfg(feature = "std")]
+use crate::fingerprint;
+
+use crate::{
+    Emitter, Observable, ReadRef, Readable, Subscribers, Unsubscribe, Writable, lock::RwLock,
+};
 
 /// A deduplicated observable value.
 ///
@@ -13,59 +20,186 @@ use crate::{Callback, Emitter, Observable, Readable, Writable};
 /// If the wrapped value implements Writable, all changes will be propagated to the target.
 pub struct Deduped<Value, Target>
 where
-    Value: PartialEq + Eq + Clone + Send + Sync,
+    Value: Clone + Send + Sync,
     Target: Readable<Value> + Emitter + Send + Sync,
 {
     target: Arc<Target>,
     value: RwLock<Value>,
-    callbacks: RwLock<HashMap<usize, Callback<Value>>>,
-    counter: RwLock<usize>,
+    subscribers: Subscribers<Value>,
+    version: AtomicU64,
 }
 
 impl<Value, Target> Deduped<Value, Target>
 where
-    Value: PartialEq + Eq + Clone + Send + Sync + 'static,
+    Value: Clone + Send + Sync + 'static,
     Target: Readable<Value> + Emitter + Send + Sync + 'static,
 {
-    /// Creates a new deduplicated value by wrapping another observable.
+    /// Creates a new deduplicated value that uses a custom comparator.
+    ///
+    /// Callbacks are only triggered when `eq` reports the new value as different from the current
+    /// value. Useful for values without `Eq`, e.g. floats, or for approximate comparisons.
     ///
     /// # Example
     ///
     /// ```
     /// use stores::{Observable, Deduped};
-    /// let observable = Observable::new(1);
-    /// let deduped = Deduped::from(observable.clone());
+    /// let observable = Observable::new(1.0_f64);
+    /// let deduped = Deduped::from_with(observable.clone(), |a, b| (a - b).abs() < 0.01);
     /// ```
-    pub fn from(target: Arc<Target>) -> Arc<Self> {
+    pub fn from_with(
+        target: Arc<Target>,
+        eq: impl Fn(&Value, &Value) -> bool + Send + Sync + 'static,
+    ) -> Arc<Self> {
         let instance = Arc::new(Self {
             target: target.clone(),
             value: RwLock::new(target.get()),
-            callbacks: RwLock::new(HashMap::new()),
-            counter: RwLock::new(0),
+            subscribers: Subscribers::new(),
+            version: AtomicU64::new(0),
         });
 
-        let _ = target.subscribe({
-            let instance = instance.clone();
+        let unsubscribe = target.subscribe({
+            let instance = Arc::downgrade(&instance);
             move |value| {
-                if *instance.value.read().unwrap() != *value {
-                    *instance.value.write().unwrap() = value.clone();
+                if let Some(instance) = instance.upgrade()
+                    && !eq(&instance.value.read(), value)
+                {
+                    *instance.value.write() = value.clone();
                     instance.notify();
                 }
             }
         });
+        instance.subscribers.hold(unsubscribe);
 
         instance
     }
 
+    /// Names the store, so its changes are reported to the global hook.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Deduped;
+    /// let deduped = Deduped::new(1);
+    /// deduped.name("deduped");
+    /// ```
+    #[cfg(feature = "debug")]
+    pub fn name(&self, name: &str)
+    where
+        Value: Debug,
+    {
+        self.subscribers.name(name);
+    }
+
+    /// Returns the version of the value, which increases with every change.
+    ///
+    /// Allows to check whether the value changed since it was last looked at, without
+    /// subscribing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Deduped, Writable};
+    /// let deduped = Deduped::new(1);
+    /// deduped.set(1);
+    /// assert_eq!(deduped.version(), 0);
+    ///
+    /// deduped.set(2);
+    /// assert_eq!(deduped.version(), 1);
+    /// ```
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Deduped};
+    /// let deduped = Deduped::new(1);
+    /// assert_eq!(deduped.subscriber_count(), 0);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count()
+    }
+
+    /// Subscribes to value changes on behalf of a weakly referenced owner.
+    ///
+    /// Only a weak reference to `owner` is kept, so the subscription does not keep it alive.
+    /// The callback is run immediately and on every change as long as the owner exists.
+    /// Once the owner is dropped, the subscription removes itself on the next change.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use stores::Deduped;
+    /// let store = Deduped::new(1);
+    /// let owner = Arc::new(Mutex::new(0));
+    /// let unsubscribe = store.subscribe_weak(&owner, |owner, value| {
+    ///     *owner.lock().unwrap() = *value;
+    /// });
+    /// ```
+    pub fn subscribe_weak<Owner>(
+        &self,
+        owner: &Arc<Owner>,
+        callback: impl Fn(&Owner, &Value) + Send + Sync + 'static,
+    ) -> Unsubscribe
+    where
+        Owner: Send + Sync + 'static,
+    {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe_weak(value, owner, callback)
+    }
+
     /// Internal function to run all registered callbacks.
     fn notify(&self) {
-        let value = self.value.read().unwrap().clone();
-        for callback in self.callbacks.read().unwrap().values() {
-            match callback {
-                Callback::Subscriber(func) => func(&value),
-                Callback::Listener(func) => func(),
-            }
-        }
+        self.version.fetch_add(1, Ordering::AcqRel);
+        let value = self.value.read().clone();
+        self.subscribers.notify(&value);
+    }
+}
+
+impl<Value, Target> Deduped<Value, Target>
+where
+    Value: PartialEq + Eq + Clone + Send + Sync + 'static,
+    Target: Readable<Value> + Emitter + Send + Sync + 'static,
+{
+    /// Creates a new deduplicated value by wrapping another observable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Deduped};
+    /// let observable = Observable::new(1);
+    /// let deduped = Deduped::from(observable.clone());
+    /// ```
+    pub fn from(target: Arc<Target>) -> Arc<Self> {
+        Self::from_with(target, |a, b| a == b)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Value, Target> Deduped<Value, Target>
+where
+    Value: Hash + Clone + Send + Sync + 'static,
+    Target: Readable<Value> + Emitter + Send + Sync + 'static,
+{
+    /// Creates a new deduplicated value that compares fingerprints instead of values.
+    ///
+    /// Useful for values that are expensive to compare but cheap to hash.
+    /// Different values can produce the same fingerprint, in which case a change is missed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Deduped, Observable};
+    /// let observable = Observable::new(vec![1, 2, 3]);
+    /// let deduped = Deduped::from_hashing(observable.clone());
+    /// ```
+    pub fn from_hashing(target: Arc<Target>) -> Arc<Self> {
+        Self::from_with(target, |a, b| fingerprint(a) == fingerprint(b))
     }
 }
 
@@ -84,82 +218,104 @@ where
     /// let deduped = Deduped::new(1);
     /// ```
     pub fn new(value: Value) -> Arc<Self> {
-        let target = Observable::new(value);
-        Self::from(target)
+        let owner = Observable::new(value);
+        Self::from(owner)
+    }
+
+    /// Creates a standalone Deduped holding the default value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Deduped, Readable};
+    /// let deduped = Deduped::<i32, _>::default();
+    /// assert_eq!(deduped.get(), 0);
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn default() -> Arc<Self>
+    where
+        Value: Default,
+    {
+        Self::new(Value::default())
+    }
+
+    /// Restores the value the internal observable was created with.
+    ///
+    /// Callbacks are only triggered if the current value differs from the initial value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Deduped, Readable, Writable};
+    /// let deduped = Deduped::new(1);
+    /// deduped.set(2);
+    ///
+    /// deduped.reset();
+    /// assert_eq!(deduped.get(), 1);
+    /// ```
+    pub fn reset(&self) {
+        self.target.reset();
     }
 }
 
 impl<Value, Target> Emitter for Deduped<Value, Target>
 where
-    Value: PartialEq + Eq + Clone + Send + Sync,
+    Value: Clone + Send + Sync + 'static,
     Target: Readable<Value> + Emitter + Send + Sync,
 {
-    fn listen(&self, callback: impl Fn() + Send + Sync + 'static) -> impl Fn() {
-        let callback = Box::new(callback);
-        let id = *self.counter.read().unwrap();
-        *self.counter.write().unwrap() += 1;
-
-        self.callbacks
-            .write()
-            .unwrap()
-            .insert(id, Callback::Listener(callback));
-        move || {
-            self.callbacks.write().unwrap().remove(&id);
-        }
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.subscribers.listen(callback)
     }
 }
 
 impl<Value, Target> Readable<Value> for Deduped<Value, Target>
 where
-    Value: PartialEq + Eq + Clone + Send + Sync + 'static,
+    Value: Clone + Send + Sync + 'static,
     Target: Readable<Value> + Emitter + Send + Sync + 'static,
 {
     fn get(&self) -> Value {
-        self.value.read().unwrap().clone()
+        self.value.read().clone()
     }
 
-    fn subscribe(&self, callback: impl Fn(&Value) + Send + Sync + 'static) -> impl Fn() {
-        let value = self.value.read().unwrap().clone();
-        callback(&value);
-
-        let callback = Box::new(callback);
-        let id = *self.counter.read().unwrap();
-        *self.counter.write().unwrap() += 1;
-
-        self.callbacks
-            .write()
-            .unwrap()
-            .insert(id, Callback::Subscriber(callback));
-
-        move || {
-            self.callbacks.write().unwrap().remove(&id);
-        }
+    fn subscribe_boxed(&self, callback: Box<dyn Fn(&Value) + Send + Sync>) -> Unsubscribe {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe(value, callback)
     }
 }
 
 impl<Value, Target> Writable<Value> for Deduped<Value, Target>
 where
-    Value: PartialEq + Eq + Clone + Send + Sync,
+    Value: Clone + Send + Sync,
     Target: Readable<Value> + Emitter + Writable<Value> + Send + Sync,
 {
     fn set(&self, value: Value) {
         self.target.set(value);
     }
 
-    fn update(&self, updater: impl Fn(&Value) -> Value + Send + Sync + 'static) {
-        self.target.update(updater);
+    fn update_boxed(&self, updater: Box<dyn Fn(&Value) -> Value + Send + Sync>) {
+        self.target.update_boxed(updater);
+    }
+}
+
+impl<Value, Target> ReadRef<Value> for Deduped<Value, Target>
+where
+    Value: Clone + Send + Sync,
+    Target: Readable<Value> + Emitter + Send + Sync,
+{
+    fn with<R>(&self, reader: impl FnOnce(&Value) -> R) -> R {
+        reader(&self.value.read())
     }
 }
 
 impl<Value, Target> Debug for Deduped<Value, Target>
 where
-    Value: Debug + PartialEq + Eq + Clone + Send + Sync,
+    Value: Debug + Clone + Send + Sync,
     Target: Readable<Value> + Emitter + Send + Sync,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Deduped")
-            .field("value", &self.value.read().unwrap())
-            .field("callbacks", &self.callbacks.read().unwrap().len())
+            .field("value", &self.value.read())
+            .field("callbacks", &self.subscribers)
             .finish()
     }
 }
@@ -333,4 +489,112 @@ mod tests {
         assert_eq!(deduped.get(), 10);
         assert_eq!(counter.lock().unwrap().clone(), 10);
     }
+
+    #[test]
+    fn it_counts_subscribers() {
+        let deduped = Deduped::new(0);
+        assert_eq!(deduped.subscriber_count(), 0);
+
+        let unsubscribe_listener = deduped.listen(|| {});
+        let unsubscribe_subscriber = deduped.subscribe(|_| {});
+        assert_eq!(deduped.subscriber_count(), 2);
+
+        unsubscribe_listener();
+        assert_eq!(deduped.subscriber_count(), 1);
+
+        unsubscribe_subscriber();
+        assert_eq!(deduped.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_provides_default() {
+        let deduped = Deduped::<i32, _>::default();
+        assert_eq!(deduped.get(), 0);
+
+        deduped.set(1);
+        assert_eq!(deduped.get(), 1);
+    }
+
+    #[test]
+    fn it_resets_to_initial_value() {
+        let deduped = Deduped::new(1);
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = deduped.subscribe({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+
+        deduped.set(2);
+        deduped.reset();
+        deduped.reset();
+
+        assert_eq!(deduped.get(), 1);
+        assert_eq!(*values.lock().unwrap(), vec![1, 2, 1]);
+    }
+
+    #[test]
+    fn it_dedups_with_custom_comparator() {
+        let observable = Observable::new(1.0_f64);
+        let deduped = Deduped::from_with(observable.clone(), |a, b| (a - b).abs() < 0.01);
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = deduped.on_change({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+
+        observable.set(1.001);
+        observable.set(1.005);
+        assert_eq!(deduped.get(), 1.0);
+
+        observable.set(1.5);
+        observable.set(1.501);
+        assert_eq!(deduped.get(), 1.5);
+        assert_eq!(*values.lock().unwrap(), vec![1.5]);
+    }
+
+    #[test]
+    fn it_advances_the_version_only_on_changes() {
+        let observable = Observable::new(1);
+        let deduped = Deduped::from(observable.clone());
+
+        observable.set(1);
+        assert_eq!(observable.version(), 1);
+        assert_eq!(deduped.version(), 0);
+
+        observable.set(2);
+        observable.set(2);
+        assert_eq!(deduped.version(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_dedups_by_fingerprint() {
+        #[derive(Clone, Hash)]
+        struct Document {
+            title: String,
+            lines: Vec<String>,
+        }
+
+        let document = Document {
+            title: String::from("title"),
+            lines: (0..1000).map(|line| line.to_string()).collect(),
+        };
+        let observable = Observable::new(document.clone());
+        let deduped = Deduped::from_hashing(observable.clone());
+        let notified = Arc::new(Mutex::new(0));
+
+        let _ = deduped.listen({
+            let notified = notified.clone();
+            move || *notified.lock().unwrap() += 1
+        });
+
+        observable.set(document.clone());
+        assert_eq!(*notified.lock().unwrap(), 0);
+
+        observable.modify(|document| document.title.push('!'));
+        assert_eq!(*notified.lock().unwrap(), 1);
+        assert_eq!(deduped.get().title, "title!");
+    }
 }
@@ -0,0 +1,348 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::{
+    boxed::Box,
+    cell::{Cell, RefCell},
+    vec::Vec,
+};
+
+use crate::lock::RwLock;
+
+type CycleHook = Box<dyn Fn(Cycle) + Send + Sync>;
+
+/// Default for the maximum number of nested recomputations on a single thread.
+const DEFAULT_LIMIT: usize = 100;
+
+static LIMIT: AtomicUsize = AtomicUsize::new(DEFAULT_LIMIT);
+
+/// Hook that is called whenever the recursion limit breaks a cycle.
+static CYCLE_HOOK: RwLock<Option<CycleHook>> = RwLock::new(None);
+
+thread_local! {
+    /// Number of recomputations currently running on this thread.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+
+    /// Number of callbacks currently running on this thread.
+    static CALLS: Cell<usize> = const { Cell::new(0) };
+
+    /// Stores currently notifying on this thread.
+    static NOTIFYING: RefCell<Vec<Notification>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Sets the maximum number of nested recomputations of derived values on a single thread.
+///
+/// Derived values that feed back into their own targets would otherwise recurse until the stack
+/// overflows. Once the limit is reached, the recomputation is skipped, which breaks the cycle,
+/// and the cycle is reported to the hook installed via `set_cycle_hook`. Defaults to 100.
+///
+/// The same limit applies to observables that are changed by their own callbacks,
+/// which are notified again at most this many times in a row.
+///
+/// # Example
+///
+/// ```
+/// use stores::set_recursion_limit;
+/// set_recursion_limit(50);
+/// ```
+pub fn set_recursion_limit(limit: usize) {
+    LIMIT.store(limit, Ordering::Relaxed);
+}
+
+/// Kind of cycle that was broken because it reached the recursion limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cycle {
+    /// Derived values recomputed each other more often than the limit allows.
+    Recomputation,
+    /// An observable was changed by its own callbacks more often than the limit allows.
+    Notification,
+}
+
+/// Installs a hook that is called whenever the recursion limit breaks a cycle.
+///
+/// The hook is called on the thread that ran into the cycle, right after it was broken.
+/// Installing a new hook replaces the previous one.
+///
+/// # Example
+///
+/// ```
+/// use stores::{Derived, Observable, Readable, Writable, set_cycle_hook};
+/// set_cycle_hook(|cycle| println!("broke a cycle: {cycle:?}"));
+///
+/// let source = Observable::new(0);
+/// let derived = Derived::new(&[source.clone()], {
+///     let source = source.clone();
+///     move || source.get() + 1
+/// });
+/// let _ = derived.subscribe(move |value| source.set(*value)); // "broke a cycle: ..."
+/// ```
+pub fn set_cycle_hook(hook: impl Fn(Cycle) + Send + Sync + 'static) {
+    *CYCLE_HOOK.write() = Some(Box::new(hook));
+}
+
+/// Removes the hook that is called whenever the recursion limit breaks a cycle.
+///
+/// # Example
+///
+/// ```
+/// use stores::clear_cycle_hook;
+/// clear_cycle_hook();
+/// ```
+pub fn clear_cycle_hook() {
+    *CYCLE_HOOK.write() = None;
+}
+
+/// Internal function to report a broken cycle to the hook.
+fn report(cycle: Cycle) {
+    if let Some(hook) = &*CYCLE_HOOK.read() {
+        hook(cycle);
+    }
+}
+
+/// Internal function to run a recomputation, unless the recursion limit is reached.
+///
+/// Returns `None` if the recomputation was skipped.
+pub(crate) fn guard<R>(func: impl FnOnce() -> R) -> Option<R> {
+    let depth = DEPTH.get();
+    let limit = LIMIT.load(Ordering::Relaxed);
+    if depth >= limit {
+        report(Cycle::Recomputation);
+        return None;
+    }
+
+    DEPTH.set(depth + 1);
+    let _guard = Guard;
+    Some(func())
+}
+
+/// Internal guard that leaves the recomputation, even if it panics.
+struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        DEPTH.set(DEPTH.get() - 1);
+    }
+}
+
+/// Round of notifications of a store, which gets the callbacks to skip.
+type Round = Box<dyn FnOnce(&[(i32, usize)])>;
+
+/// Internal state of a store that is currently notifying its callbacks.
+struct Notification {
+    store: usize,
+    /// The round queued by changing the store again while notifying.
+    pending: Option<Round>,
+    /// The callback that is currently running, along with the number of callbacks it runs in.
+    running: Option<((i32, usize), usize)>,
+    /// Callbacks that changed the store while notifying.
+    writers: Vec<(i32, usize)>,
+}
+
+/// Internal function to run the notifications of a store, queueing re-entrant ones.
+///
+/// If the store is changed again by one of its own callbacks, the nested round is not run
+/// immediately, but once the current one completes, replacing any other queued one. The
+/// callbacks that made the change themselves are skipped in that round, as they already know
+/// about it. A callback that always writes the same value therefore only runs once. Changes
+/// beyond the recursion limit are not notified anymore.
+///
+/// Returns `false` if the recursion limit was reached.
+pub(crate) fn notify(store: usize, round: impl FnOnce(&[(i32, usize)]) + 'static) -> bool {
+    let round = NOTIFYING.with_borrow_mut(|notifications| {
+        match notifications.iter_mut().find(|entry| entry.store == store) {
+            Some(entry) => {
+                entry.pending = Some(Box::new(round));
+                // Changes made through the callbacks of other stores do not count as writes.
+                let calls = CALLS.get();
+                entry.writers.extend(
+                    entry
+                        .running
+                        .filter(|(_, level)| *level == calls)
+                        .map(|(id, _)| id),
+                );
+                None
+            }
+            None => {
+                notifications.push(Notification {
+                    store,
+                    pending: None,
+                    running: None,
+                    writers: Vec::new(),
+                });
+                Some(round)
+            }
+        }
+    });
+
+    let Some(round) = round else {
+        return true;
+    };
+
+    let _guard = Notifying(store);
+    let limit = LIMIT.load(Ordering::Relaxed);
+    let mut round: Round = Box::new(round);
+    let mut skipped = Vec::new();
+    for _ in 0..limit {
+        round(&skipped);
+
+        let pending = NOTIFYING.with_borrow_mut(|notifications| {
+            let entry = notifications
+                .iter_mut()
+                .find(|entry| entry.store == store)?;
+            skipped = core::mem::take(&mut entry.writers);
+            entry.pending.take()
+        });
+        match pending {
+            Some(pending) => round = pending,
+            None => return true,
+        }
+    }
+
+    report(Cycle::Notification);
+    false
+}
+
+/// Internal function to check whether a store is currently notifying its callbacks.
+pub(crate) fn is_notifying(store: usize) -> bool {
+    NOTIFYING.with_borrow(|notifications| notifications.iter().any(|entry| entry.store == store))
+}
+
+/// Internal function to run a callback of a notifying store, remembering it as the running one.
+pub(crate) fn running<R>(store: usize, id: (i32, usize), func: impl FnOnce() -> R) -> R {
+    let swap = |running: Option<((i32, usize), usize)>| {
+        NOTIFYING.with_borrow_mut(|notifications| {
+            notifications
+                .iter_mut()
+                .find(|entry| entry.store == store)
+                .and_then(|entry| core::mem::replace(&mut entry.running, running))
+        })
+    };
+
+    let previous = swap(Some((id, CALLS.get())));
+    let _guard = Running(store, previous);
+    func()
+}
+
+/// Internal function to run a callback of any store, counting how many are nested.
+pub(crate) fn calling<R>(func: impl FnOnce() -> R) -> R {
+    CALLS.set(CALLS.get() + 1);
+    let _guard = Calling;
+    func()
+}
+
+/// Internal guard that leaves the callback, even if it panics.
+struct Calling;
+
+impl Drop for Calling {
+    fn drop(&mut self) {
+        CALLS.set(CALLS.get() - 1);
+    }
+}
+
+/// Internal guard that ends the notifications of a store, even if a callback panics.
+struct Notifying(usize);
+
+impl Drop for Notifying {
+    fn drop(&mut self) {
+        NOTIFYING
+            .with_borrow_mut(|notifications| notifications.retain(|entry| entry.store != self.0));
+    }
+}
+
+/// Internal guard that restores the previously running callback, even if a callback panics.
+struct Running(usize, Option<((i32, usize), usize)>);
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        NOTIFYING.with_borrow_mut(|notifications| {
+            if let Some(entry) = notifications.iter_mut().find(|entry| entry.store == self.0) {
+                entry.running = self.1;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    fn it_tracks_nested_recomputations() {
+        fn nest(levels: usize) -> usize {
+            match guard(|| nest(levels + 1)) {
+                Some(levels) => levels,
+                None => levels,
+            }
+        }
+
+        assert_eq!(nest(0), DEFAULT_LIMIT);
+        assert_eq!(DEPTH.get(), 0);
+    }
+
+    /// Skipped callbacks of every round that ran.
+    type Rounds = Rc<RefCell<Vec<Vec<(i32, usize)>>>>;
+
+    /// Decides by the skipped callbacks and the number of rounds whether to change the store again.
+    type Change = fn(&[(i32, usize)], usize, &dyn Fn());
+
+    /// Round that records the skipped callbacks and changes the store again through `change`.
+    fn round(rounds: &Rounds, change: Change) -> impl FnOnce(&[(i32, usize)]) + 'static {
+        let rounds = rounds.clone();
+        move |skipped| {
+            rounds.borrow_mut().push(skipped.to_vec());
+            let count = rounds.borrow().len();
+            change(skipped, count, &|| {
+                notify(0, round(&rounds, change));
+            });
+        }
+    }
+
+    #[test]
+    fn it_queues_reentrant_notifications() {
+        let rounds = Rc::new(RefCell::new(Vec::new()));
+        let completed = notify(
+            0,
+            round(&rounds, |_, count, again| {
+                if count < 3 {
+                    again();
+                }
+            }),
+        );
+
+        assert!(completed);
+        assert_eq!(rounds.borrow().len(), 3);
+        assert!(NOTIFYING.with_borrow(Vec::is_empty));
+    }
+
+    #[test]
+    fn it_skips_the_callbacks_that_changed_the_store() {
+        let rounds = Rc::new(RefCell::new(Vec::new()));
+        let completed = notify(
+            0,
+            round(&rounds, |skipped, _, again| {
+                if !skipped.contains(&(0, 1)) {
+                    running(0, (0, 1), again);
+                }
+            }),
+        );
+
+        assert!(completed);
+        assert_eq!(*rounds.borrow(), vec![vec![], vec![(0, 1)]]);
+    }
+
+    #[test]
+    fn it_does_not_skip_callbacks_that_changed_the_store_indirectly() {
+        let rounds = Rc::new(RefCell::new(Vec::new()));
+        let completed = notify(
+            0,
+            round(&rounds, |_, count, again| {
+                if count < 2 {
+                    running(0, (0, 1), || calling(again));
+                }
+            }),
+        );
+
+        assert!(completed);
+        assert_eq!(*rounds.borrow(), vec![vec![], vec![]]);
+    }
+}
@@ -1,20 +1,32 @@
-use std::{
-    collections::HashMap,
+use alloc::{boxed::Box, sync::Arc};
+#[cfg(feature = "std")]
+use core::sync::atomic::AtomicBool;
+use core::{
     fmt::Debug,
-    sync::{Arc, RwLock},
+    sync::atomic::{AtomicU64, Ordering},
 };
 
-use crate::{Callback, Emitter, Readable};
+use crate::{
+    Emitter, ReadRef, Readable, Subscribers, Unsubscribe, as_emitter,
+    lock::{Mutex, RwLock},
+};
+#[cfg(feature = "std")]
+use crate::{batch, depth};
 
 /// A readable observable value that is derived from other observables.
+///
+/// Targets only keep a weak reference, the derived value is dropped along with its last handle.
 pub struct Derived<Value>
 where
     Value: Clone + Send + Sync,
 {
     value: RwLock<Value>,
     compute: Box<dyn Fn() -> Value + Send + Sync>,
-    callbacks: RwLock<HashMap<usize, Callback<Value>>>,
-    counter: RwLock<usize>,
+    refresh: fn(&Self),
+    subscribers: Subscribers<Value>,
+    version: AtomicU64,
+    #[cfg(feature = "std")]
+    dirty: AtomicBool,
 }
 
 impl<Value> Derived<Value>
@@ -23,6 +35,8 @@ where
 {
     /// Creates a new derived value.
     ///
+    /// The value is recomputed whenever one of the targets changes.
+    /// Targets can be any mix of emitters, e.g. observables and other derived values.
     /// The result is wrapped inside an Arc to be easily transferable.
     ///
     /// # Example
@@ -38,87 +52,483 @@ where
     /// });
     /// ```
     pub fn new(
-        targets: &[Arc<impl Emitter + Send + Sync + 'static>],
+        targets: &[Arc<dyn Emitter + Send + Sync>],
+        compute: impl Fn() -> Value + Send + Sync + 'static,
+    ) -> Arc<Self> {
+        Self::from_targets(targets.iter().cloned(), compute)
+    }
+
+    /// Creates a new derived value from any number of targets.
+    ///
+    /// Like `new`, but takes the targets by value, e.g. from a dynamically built `Vec`.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use stores::{Derived, Emitter, Observable, Readable};
+    /// let inputs: Vec<_> = (0..10).map(Observable::new).collect();
+    /// let sum = Derived::from_targets(
+    ///     inputs.iter().map(|input| input.clone() as Arc<dyn Emitter + Send + Sync>),
+    ///     {
+    ///         let inputs = inputs.clone();
+    ///         move || inputs.iter().map(|input| input.get()).sum::<i32>()
+    ///     },
+    /// );
+    /// assert_eq!(sum.get(), 45);
+    /// ```
+    pub fn from_targets(
+        targets: impl IntoIterator<Item = Arc<dyn Emitter + Send + Sync>>,
         compute: impl Fn() -> Value + Send + Sync + 'static,
     ) -> Arc<Self> {
         let value = compute();
+        Self::from_value(targets, value, compute, Self::refresh)
+    }
+
+    /// Creates a new derived value that starts with the given value.
+    ///
+    /// Unlike `new`, the computation is not run at construction, but only once a target changes.
+    /// Useful for expensive computations, or ones that need the targets to be ready first.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Derived, Observable, Readable, Writable};
+    /// let a = Observable::new(1);
+    /// let doubled = Derived::new_lazy(&[a.clone()], 0, {
+    ///     let a = a.clone();
+    ///     move || a.get() * 2
+    /// });
+    /// assert_eq!(doubled.get(), 0);
+    ///
+    /// a.set(2);
+    /// assert_eq!(doubled.get(), 4);
+    /// ```
+    pub fn new_lazy(
+        targets: &[Arc<dyn Emitter + Send + Sync>],
+        initial: Value,
+        compute: impl Fn() -> Value + Send + Sync + 'static,
+    ) -> Arc<Self> {
+        Self::from_value(targets.iter().cloned(), initial, compute, Self::refresh)
+    }
 
+    /// Creates a new derived value that only recomputes when its inputs changed.
+    ///
+    /// Whenever a target changes, the key function is run first. The computation only runs
+    /// if the key differs from the one of the last computation, otherwise the cached value is
+    /// kept. Callbacks are still run on every target change, use `new_deduped` or `dedup`
+    /// to skip those as well.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Derived, Observable, Readable, Writable};
+    /// let a = Observable::new(3);
+    /// let factorial = Derived::new_memoized(
+    ///     &[a.clone()],
+    ///     {
+    ///         let a = a.clone();
+    ///         move || a.get()
+    ///     },
+    ///     |n| (1..=*n).product::<u64>(),
+    /// );
+    /// assert_eq!(factorial.get(), 6);
+    ///
+    /// a.set(4);
+    /// assert_eq!(factorial.get(), 24);
+    /// ```
+    pub fn new_memoized<Key>(
+        targets: &[Arc<dyn Emitter + Send + Sync>],
+        key: impl Fn() -> Key + Send + Sync + 'static,
+        compute: impl Fn(&Key) -> Value + Send + Sync + 'static,
+    ) -> Arc<Self>
+    where
+        Key: PartialEq + Send + 'static,
+    {
+        let cache = Mutex::new(None::<(Key, Value)>);
+        Self::new(targets, move || {
+            let key = key();
+            let mut cache = cache.lock();
+            match &*cache {
+                Some((cached, value)) if *cached == key => value.clone(),
+                _ => {
+                    let value = compute(&key);
+                    *cache = Some((key, value.clone()));
+                    value
+                }
+            }
+        })
+    }
+
+    /// Internal function to create a new derived value with an already known value.
+    ///
+    /// The `refresh` strategy is run whenever one of the targets changes.
+    fn from_value(
+        targets: impl IntoIterator<Item = Arc<dyn Emitter + Send + Sync>>,
+        value: Value,
+        compute: impl Fn() -> Value + Send + Sync + 'static,
+        refresh: fn(&Self),
+    ) -> Arc<Self> {
         let instance = Arc::new(Self {
             value: RwLock::new(value),
             compute: Box::new(compute),
-            callbacks: RwLock::new(HashMap::new()),
-            counter: RwLock::new(0),
+            refresh,
+            subscribers: Subscribers::new(),
+            version: AtomicU64::new(0),
+            #[cfg(feature = "std")]
+            dirty: AtomicBool::new(false),
         });
 
         for target in targets {
-            let _unsubscribe = target.listen({
-                let instance = instance.clone();
-                move || {
-                    let new_value = (instance.compute)();
-                    *instance.value.write().unwrap() = new_value.clone();
+            instance.listen_weak(&*target);
+        }
+
+        instance
+    }
+
+    /// Creates a new derived value without any targets.
+    ///
+    /// Targets can be attached later on via `depend_on`.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Derived, Observable, Readable, Writable};
+    /// let a = Observable::new(1);
+    /// let doubled = Derived::standalone({
+    ///     let a = a.clone();
+    ///     move || a.get() * 2
+    /// });
+    ///
+    /// doubled.depend_on(&*a);
+    /// a.set(2);
+    /// assert_eq!(doubled.get(), 4);
+    /// ```
+    pub fn standalone(compute: impl Fn() -> Value + Send + Sync + 'static) -> Arc<Self> {
+        Self::new(&[], compute)
+    }
+
+    /// Attaches another target after construction.
+    ///
+    /// The value is recomputed whenever the target changes, just like for the initial targets.
+    /// Only a weak reference is kept by the target, and it is unsubscribed from once this
+    /// derived value is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Derived, Observable};
+    /// let a = Observable::new(1);
+    /// let derived = Derived::standalone(|| 1);
+    /// derived.depend_on(&*a);
+    /// ```
+    pub fn depend_on(self: &Arc<Self>, target: &(impl Emitter + ?Sized)) {
+        self.listen_weak(target);
+    }
 
-                    instance.notify();
+    /// Chains another derived value that is computed from this one.
+    ///
+    /// The new value only listens to this derived value, not to its targets, and keeps it alive.
+    /// Dropping the end of a chain therefore tears down the whole chain.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Derived, Observable, Readable, Writable};
+    /// let a = Observable::new(1);
+    /// let doubled = Derived::new(&[a.clone()], {
+    ///     let a = a.clone();
+    ///     move || a.get() * 2
+    /// });
+    /// let label = doubled.then(|value| format!("{} items", value));
+    ///
+    /// a.set(2);
+    /// assert_eq!(label.get(), "4 items");
+    /// ```
+    pub fn then<Out>(
+        self: &Arc<Self>,
+        map: impl Fn(Value) -> Out + Send + Sync + 'static,
+    ) -> Arc<Derived<Out>>
+    where
+        Out: Clone + Send + Sync + 'static,
+    {
+        Derived::new(&[as_emitter(self)], {
+            let parent = self.clone();
+            move || map(parent.get())
+        })
+    }
+
+    /// Internal function to recompute on changes of the target, which only keeps a weak reference.
+    ///
+    /// Targets therefore do not keep the derived value alive, and once it is dropped,
+    /// the listener is removed from every target.
+    fn listen_weak(self: &Arc<Self>, target: &(impl Emitter + ?Sized)) {
+        let unsubscribe = target.listen_boxed(Box::new({
+            let instance = Arc::downgrade(self);
+            move || {
+                if let Some(instance) = instance.upgrade() {
+                    instance.schedule(instance.refresh);
                 }
-            });
+            }
+        }));
+
+        self.subscribers.hold(unsubscribe);
+    }
+
+    /// Names the store, so its changes are reported to the global hook.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Derived, Event};
+    /// let derived = Derived::new(&[Event::new()], || 1);
+    /// derived.name("derived");
+    /// ```
+    #[cfg(feature = "debug")]
+    pub fn name(&self, name: &str)
+    where
+        Value: Debug,
+    {
+        self.subscribers.name(name);
+    }
+
+    /// Returns the version of the value, which increases with every change.
+    ///
+    /// Allows to check whether the value changed since it was last looked at, without
+    /// subscribing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Derived, Observable, Readable, Writable};
+    /// let a = Observable::new(1);
+    /// let doubled = Derived::new(&[a.clone()], {
+    ///     let a = a.clone();
+    ///     move || a.get() * 2
+    /// });
+    ///
+    /// a.set(2);
+    /// assert_eq!(doubled.version(), 1);
+    /// ```
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Derived, Readable};
+    /// let a = Observable::new(1);
+    /// let doubled = Derived::new(&[a.clone()], {
+    ///     let a = a.clone();
+    ///     move || a.get() * 2
+    /// });
+    /// assert_eq!(doubled.subscriber_count(), 0);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count()
+    }
+
+    /// Subscribes to value changes on behalf of a weakly referenced owner.
+    ///
+    /// Only a weak reference to `owner` is kept, so the subscription does not keep it alive.
+    /// The callback is run immediately and on every change as long as the owner exists.
+    /// Once the owner is dropped, the subscription removes itself on the next change.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use stores::{Derived, Event};
+    /// let store = Derived::new(&[Event::new()], || 1);
+    /// let owner = Arc::new(Mutex::new(0));
+    /// let unsubscribe = store.subscribe_weak(&owner, |owner, value| {
+    ///     *owner.lock().unwrap() = *value;
+    /// });
+    /// ```
+    pub fn subscribe_weak<Owner>(
+        &self,
+        owner: &Arc<Owner>,
+        callback: impl Fn(&Owner, &Value) + Send + Sync + 'static,
+    ) -> Unsubscribe
+    where
+        Owner: Send + Sync + 'static,
+    {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe_weak(value, owner, callback)
+    }
+
+    /// Forces the value to be recomputed and returns it.
+    ///
+    /// Does not wait for the owners to emit a change.
+    /// Calling this will trigger all registered callbacks once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use stores::Derived;
+    /// let source = Arc::new(Mutex::new(1));
+    /// let doubled = Derived::new(&[], {
+    ///     let source = source.clone();
+    ///     move || *source.lock().unwrap() * 2
+    /// });
+    ///
+    /// *source.lock().unwrap() = 2;
+    /// assert_eq!(doubled.recompute(), 4);
+    /// ```
+    pub fn recompute(&self) -> Value {
+        let value = (self.compute)();
+        *self.value.write() = value.clone();
+        self.notify();
+        value
+    }
+
+    /// Recomputes the value and notifies as if one of the targets had changed.
+    ///
+    /// Useful for values that depend on state which is not observable, like a clock or a file.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use stores::{Derived, Readable};
+    /// let source = Arc::new(AtomicUsize::new(1));
+    /// let derived = Derived::new(&[], {
+    ///     let source = source.clone();
+    ///     move || source.load(Ordering::Relaxed)
+    /// });
+    ///
+    /// source.store(2, Ordering::Relaxed);
+    /// derived.invalidate();
+    /// assert_eq!(derived.get(), 2);
+    /// ```
+    pub fn invalidate(&self) {
+        self.refresh();
+    }
+
+    /// Internal function to react to a change of one of the targets.
+    ///
+    /// Inside a batch, the value is only refreshed once, when the batch ends.
+    /// Nested refreshes beyond the recursion limit are skipped.
+    fn schedule(self: &Arc<Self>, refresh: fn(&Self)) {
+        #[cfg(feature = "std")]
+        if batch::is_batching() {
+            if !self.dirty.swap(true, Ordering::AcqRel) {
+                let instance = self.clone();
+                batch::defer(move || {
+                    instance.dirty.store(false, Ordering::Release);
+                    refresh(&instance);
+                });
+            }
+            return;
         }
 
-        instance
+        // Feedback loops between derived values and their targets are cut off at the limit.
+        #[cfg(feature = "std")]
+        let _ = depth::guard(|| refresh(self));
+        #[cfg(not(feature = "std"))]
+        refresh(self);
+    }
+
+    /// Internal function to recompute the value and run all registered callbacks.
+    fn refresh(&self) {
+        *self.value.write() = (self.compute)();
+        self.notify();
     }
 
     /// Internal function to run all registered callbacks.
     fn notify(&self) {
-        let value = self.value.read().unwrap().clone();
-        for callback in self.callbacks.read().unwrap().values() {
-            match callback {
-                Callback::Subscriber(func) => func(&value),
-                Callback::Listener(func) => func(),
-            }
+        self.version.fetch_add(1, Ordering::AcqRel);
+        let value = self.value.read().clone();
+        self.subscribers.notify(&value);
+    }
+}
+
+impl<Value> Derived<Value>
+where
+    Value: PartialEq + Clone + Send + Sync + 'static,
+{
+    /// Creates a new derived value that only notifies on actual changes.
+    ///
+    /// The value is recomputed whenever a target changes,
+    /// but callbacks are only run when the result differs from the current value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Derived, Readable};
+    /// let a = Observable::new(1);
+    /// let b = Observable::new(2);
+    /// let sum = Derived::new_deduped(&[a.clone(), b.clone()], {
+    ///     let a = a.clone();
+    ///     let b = b.clone();
+    ///     move || a.get() + b.get()
+    /// });
+    /// ```
+    pub fn new_deduped(
+        targets: &[Arc<dyn Emitter + Send + Sync>],
+        compute: impl Fn() -> Value + Send + Sync + 'static,
+    ) -> Arc<Self> {
+        let value = compute();
+        Self::from_value(
+            targets.iter().cloned(),
+            value,
+            compute,
+            Self::refresh_deduped,
+        )
+    }
+
+    /// Internal function to recompute the value and only notify on actual changes.
+    fn refresh_deduped(&self) {
+        let new_value = (self.compute)();
+        if *self.value.read() == new_value {
+            return;
         }
+
+        *self.value.write() = new_value;
+        self.notify();
     }
 }
 
 impl<Value> Emitter for Derived<Value>
 where
-    Value: Clone + Send + Sync,
+    Value: Clone + Send + Sync + 'static,
 {
-    fn listen(&self, callback: impl Fn() + Send + Sync + 'static) -> impl Fn() {
-        let callback = Box::new(callback);
-        let id = *self.counter.read().unwrap();
-        *self.counter.write().unwrap() += 1;
-
-        self.callbacks
-            .write()
-            .unwrap()
-            .insert(id, Callback::Listener(callback));
-        move || {
-            self.callbacks.write().unwrap().remove(&id);
-        }
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.subscribers.listen(callback)
     }
 }
 
 impl<Value> Readable<Value> for Derived<Value>
 where
-    Value: Clone + Send + Sync,
+    Value: Clone + Send + Sync + 'static,
 {
     fn get(&self) -> Value {
-        self.value.read().unwrap().clone()
+        self.value.read().clone()
     }
 
-    fn subscribe(&self, callback: impl Fn(&Value) + Send + Sync + 'static) -> impl Fn() {
-        let value = self.value.read().unwrap().clone();
-        callback(&value);
-
-        let callback = Box::new(callback);
-        let id = *self.counter.read().unwrap();
-        *self.counter.write().unwrap() += 1;
+    fn subscribe_boxed(&self, callback: Box<dyn Fn(&Value) + Send + Sync>) -> Unsubscribe {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe(value, callback)
+    }
+}
 
-        self.callbacks
-            .write()
-            .unwrap()
-            .insert(id, Callback::Subscriber(callback));
-        move || {
-            self.callbacks.write().unwrap().remove(&id);
-        }
+impl<Value> ReadRef<Value> for Derived<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    fn with<R>(&self, reader: impl FnOnce(&Value) -> R) -> R {
+        reader(&self.value.read())
     }
 }
 
@@ -126,17 +536,25 @@ impl<Value> Debug for Derived<Value>
 where
     Value: Debug + Clone + Send + Sync,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Derived")
-            .field("value", &self.value.read().unwrap())
-            .field("callbacks", &self.callbacks.read().unwrap().len())
+            .field("value", &self.value.read())
+            .field("callbacks", &self.subscribers)
             .finish()
     }
 }
 
 #[cfg(test)]
+// Clippy suggests `slice::from_ref` for single targets, which could not be coerced into the slice.
+#[allow(clippy::cloned_ref_to_slice_refs)]
 mod tests {
-    use std::{sync::Mutex, thread};
+    use std::{
+        sync::{
+            Mutex,
+            atomic::{AtomicUsize, Ordering},
+        },
+        thread,
+    };
 
     use crate::{Observable, Readable, Writable};
 
@@ -327,4 +745,352 @@ mod tests {
         assert_eq!(derived.get(), 20);
         assert_eq!(counter.lock().unwrap().clone(), 10);
     }
+
+    #[test]
+    fn it_counts_subscribers() {
+        let observable = Observable::new(0);
+        let derived = Derived::new(&[as_emitter(&observable)], {
+            let observable = observable.clone();
+            move || observable.get() * 2
+        });
+        assert_eq!(derived.subscriber_count(), 0);
+
+        let unsubscribe_listener = derived.listen(|| {});
+        let unsubscribe_subscriber = derived.subscribe(|_| {});
+        assert_eq!(derived.subscriber_count(), 2);
+
+        unsubscribe_listener();
+        assert_eq!(derived.subscriber_count(), 1);
+
+        unsubscribe_subscriber();
+        assert_eq!(derived.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_derives_deduped() {
+        let observable_a = Observable::new(1);
+        let observable_b = Observable::new(2);
+        let derived = Derived::new_deduped(&[observable_a.clone(), observable_b.clone()], {
+            let observable_a = observable_a.clone();
+            let observable_b = observable_b.clone();
+            move || observable_a.get() < observable_b.get()
+        });
+
+        let counter = Arc::new(Mutex::new(0));
+        let _ = derived.listen({
+            let counter = counter.clone();
+            move || {
+                *counter.lock().unwrap() += 1;
+            }
+        });
+
+        observable_a.set(0);
+        observable_b.set(5);
+        assert!(derived.get());
+        assert_eq!(counter.lock().unwrap().clone(), 0);
+
+        observable_a.set(10);
+        assert!(!derived.get());
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+    }
+
+    #[test]
+    fn it_reads_by_reference() {
+        let observable = Observable::new(2);
+        let derived = Derived::new(&[as_emitter(&observable)], {
+            let observable = observable.clone();
+            move || vec![observable.get(); observable.get()]
+        });
+
+        assert_eq!(derived.with(|items| items.len()), 2);
+
+        observable.set(5);
+        assert_eq!(derived.with(|items| items.len()), 5);
+    }
+
+    #[test]
+    fn it_derives_from_different_targets() {
+        let observable = Observable::new(2);
+        let doubled = Derived::new(&[as_emitter(&observable)], {
+            let observable = observable.clone();
+            move || observable.get() * 2
+        });
+        let label = Observable::new(String::from("value"));
+
+        let derived = Derived::new(&[doubled.clone(), label.clone()], {
+            let doubled = doubled.clone();
+            let label = label.clone();
+            move || format!("{}: {}", label.get(), doubled.get())
+        });
+
+        assert_eq!(derived.get(), "value: 4");
+
+        observable.set(3);
+        assert_eq!(derived.get(), "value: 6");
+
+        label.set(String::from("result"));
+        assert_eq!(derived.get(), "result: 6");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn it_recomputes_once_per_batch() {
+        let a = Observable::new(1);
+        let b = Observable::new(2);
+        let c = Observable::new(3);
+        let computations = Arc::new(Mutex::new(0));
+        let notifications = Arc::new(Mutex::new(0));
+
+        let sum = Derived::new(&[a.clone(), b.clone(), c.clone()], {
+            let (a, b, c) = (a.clone(), b.clone(), c.clone());
+            let computations = computations.clone();
+            move || {
+                *computations.lock().unwrap() += 1;
+                a.get() + b.get() + c.get()
+            }
+        });
+        let _ = sum.listen({
+            let notifications = notifications.clone();
+            move || *notifications.lock().unwrap() += 1
+        });
+
+        crate::batch(|| {
+            a.set(10);
+            b.set(20);
+            c.set(30);
+        });
+
+        assert_eq!(sum.get(), 60);
+        assert_eq!(computations.lock().unwrap().clone(), 2);
+        assert_eq!(notifications.lock().unwrap().clone(), 1);
+    }
+
+    #[test]
+    fn it_recomputes_on_demand() {
+        let source = Observable::new(1);
+        let doubled = Derived::new(&[], {
+            let source = source.clone();
+            move || source.get() * 2
+        });
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = doubled.subscribe({
+            let counter = counter.clone();
+            move |value| *counter.lock().unwrap() = *value
+        });
+
+        source.set(2);
+        assert_eq!(doubled.snapshot(), 2);
+
+        assert_eq!(doubled.recompute(), 4);
+        assert_eq!(doubled.snapshot(), 4);
+        assert_eq!(counter.lock().unwrap().clone(), 4);
+    }
+
+    #[test]
+    fn it_recomputes_when_invalidated() {
+        let source = Arc::new(AtomicUsize::new(1));
+        let derived = Derived::new(&[], {
+            let source = source.clone();
+            move || source.load(Ordering::Relaxed)
+        });
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = derived.on_change({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+
+        source.store(2, Ordering::Relaxed);
+        assert_eq!(derived.get(), 1);
+
+        derived.invalidate();
+        assert_eq!(derived.get(), 2);
+        assert_eq!(*values.lock().unwrap(), vec![2]);
+    }
+
+    #[test]
+    fn it_depends_on_later_targets() {
+        let a = Observable::new(1);
+        let doubled = Derived::standalone({
+            let a = a.clone();
+            move || a.get() * 2
+        });
+
+        a.set(2);
+        assert_eq!(doubled.get(), 2);
+
+        doubled.depend_on(&*a);
+        a.set(3);
+        assert_eq!(doubled.get(), 6);
+
+        drop(doubled);
+        assert_eq!(a.subscriber_count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_breaks_dependency_cycles() {
+        let source = Observable::new(0);
+        let derived = Derived::new(&[as_emitter(&source)], {
+            let source = source.clone();
+            move || source.get() + 1
+        });
+
+        // Writing the derived value back into its own target forms a cycle.
+        let _ = derived.listen({
+            let source = Arc::downgrade(&source);
+            let derived = Arc::downgrade(&derived);
+            move || {
+                if let (Some(source), Some(derived)) = (source.upgrade(), derived.upgrade()) {
+                    source.set(derived.get());
+                }
+            }
+        });
+
+        // Other tests may break cycles of their own at the same time.
+        let cycles = Arc::new(Mutex::new(Vec::new()));
+        crate::set_cycle_hook({
+            let cycles = cycles.clone();
+            let current = thread::current().id();
+            move |cycle| {
+                if thread::current().id() == current {
+                    cycles.lock().unwrap().push(cycle);
+                }
+            }
+        });
+
+        // The innermost refresh is skipped, which leaves the last written value in both stores.
+        source.set(1);
+        crate::clear_cycle_hook();
+        assert!(derived.get() > 1);
+        assert_eq!(derived.get(), source.get());
+        assert_eq!(*cycles.lock().unwrap(), vec![crate::Cycle::Notification]);
+    }
+
+    #[test]
+    fn it_derives_from_many_targets() {
+        let inputs: Vec<_> = (0..20).map(Observable::new).collect();
+        let targets: Vec<Arc<dyn Emitter + Send + Sync>> = inputs
+            .iter()
+            .map(|input| input.clone() as Arc<dyn Emitter + Send + Sync>)
+            .collect();
+        let sum = Derived::from_targets(targets, {
+            let inputs = inputs.clone();
+            move || inputs.iter().map(|input| input.get()).sum::<i32>()
+        });
+        assert_eq!(sum.get(), 190);
+
+        inputs[0].set(10);
+        inputs[19].set(0);
+        assert_eq!(sum.get(), 181);
+    }
+
+    #[test]
+    fn it_advances_the_version_on_every_recomputation() {
+        let a = Observable::new(1);
+        let parity = Derived::new_deduped(&[as_emitter(&a)], {
+            let a = a.clone();
+            move || a.get() % 2
+        });
+        assert_eq!(parity.version(), 0);
+
+        a.set(3);
+        assert_eq!(parity.version(), 0);
+
+        a.set(4);
+        parity.invalidate();
+        assert_eq!(parity.version(), 2);
+    }
+
+    #[test]
+    fn it_derives_lazily() {
+        let a = Observable::new(1);
+        let computations = Arc::new(AtomicUsize::new(0));
+        let doubled = Derived::new_lazy(&[as_emitter(&a)], 0, {
+            let a = a.clone();
+            let computations = computations.clone();
+            move || {
+                computations.fetch_add(1, Ordering::Relaxed);
+                a.get() * 2
+            }
+        });
+
+        assert_eq!(doubled.get(), 0);
+        assert_eq!(computations.load(Ordering::Relaxed), 0);
+
+        a.set(2);
+        assert_eq!(doubled.get(), 4);
+        assert_eq!(computations.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn it_skips_recomputation_for_unchanged_keys() {
+        let observable = Observable::new(1);
+        let computations = Arc::new(AtomicUsize::new(0));
+        let derived = Derived::new_memoized(
+            &[as_emitter(&observable)],
+            {
+                let observable = observable.clone();
+                move || observable.get() / 10
+            },
+            {
+                let computations = computations.clone();
+                move |key| {
+                    computations.fetch_add(1, Ordering::SeqCst);
+                    key * 100
+                }
+            },
+        );
+        assert_eq!(derived.get(), 0);
+
+        observable.set(5);
+        assert_eq!(computations.load(Ordering::SeqCst), 1);
+
+        observable.set(25);
+        assert_eq!(derived.get(), 200);
+        assert_eq!(computations.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn it_detaches_from_targets_once_dropped() {
+        let observable = Observable::new(1);
+        let computations = Arc::new(AtomicUsize::new(0));
+        let derived = Derived::new(&[as_emitter(&observable)], {
+            let observable = observable.clone();
+            let computations = computations.clone();
+            move || {
+                computations.fetch_add(1, Ordering::SeqCst);
+                observable.get() * 2
+            }
+        });
+        assert_eq!(observable.subscriber_count(), 1);
+
+        drop(derived);
+        assert_eq!(observable.subscriber_count(), 0);
+
+        observable.set(2);
+        assert_eq!(computations.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn it_chains_derived_values() {
+        let observable = Observable::new(1);
+        let first = Derived::new(&[as_emitter(&observable)], {
+            let observable = observable.clone();
+            move || observable.get() + 1
+        });
+        let tail = first
+            .then(|value| value * 10)
+            .then(|value| value.to_string());
+        drop(first);
+        assert_eq!(tail.get(), "20");
+
+        observable.set(4);
+        assert_eq!(tail.get(), "50");
+        assert_eq!(observable.subscriber_count(), 1);
+
+        drop(tail);
+        assert_eq!(observable.subscriber_count(), 0);
+    }
 }
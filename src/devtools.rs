@@ -0,0 +1,126 @@
+use alloc::{boxed::Box, string::String};
+use core::fmt::Debug;
+
+use crate::lock::RwLock;
+
+type Hook = Box<dyn Fn(&str, &dyn Debug) + Send + Sync>;
+type Reporter<Value> = Box<dyn Fn(&Value) + Send + Sync>;
+
+/// Global hook that is called whenever a named store notifies its callbacks.
+static HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+
+/// Installs a global hook that observes all changes of named stores.
+///
+/// The hook receives the name of the store and its new value.
+/// Only stores that have been given a name via `name` are reported.
+/// Installing a new hook replaces the previous one.
+///
+/// # Example
+///
+/// ```
+/// use stores::{Observable, Writable, set_global_hook};
+/// set_global_hook(|name, value| println!("{name}: {value:?}"));
+///
+/// let observable = Observable::new(1);
+/// observable.name("counter");
+/// observable.set(2); // "counter: 2"
+/// ```
+pub fn set_global_hook(hook: impl Fn(&str, &dyn Debug) + Send + Sync + 'static) {
+    *HOOK.write() = Some(Box::new(hook));
+}
+
+/// Removes the global hook.
+///
+/// # Example
+///
+/// ```
+/// use stores::clear_global_hook;
+/// clear_global_hook();
+/// ```
+pub fn clear_global_hook() {
+    *HOOK.write() = None;
+}
+
+/// Internal helper that reports the values of a named store to the global hook.
+pub(crate) struct Inspector<Value> {
+    reporter: RwLock<Option<Reporter<Value>>>,
+}
+
+impl<Value> Inspector<Value> {
+    /// Creates a new inspector that does not report until it is named.
+    pub(crate) fn new() -> Self {
+        Self {
+            reporter: RwLock::new(None),
+        }
+    }
+
+    /// Names the inspector, which starts reporting to the global hook.
+    pub(crate) fn name(&self, name: &str)
+    where
+        Value: Debug,
+    {
+        let name = String::from(name);
+        *self.reporter.write() = Some(Box::new(move |value| {
+            if let Some(hook) = &*HOOK.read() {
+                hook(&name, value);
+            }
+        }));
+    }
+
+    /// Reports the value to the global hook if the inspector is named.
+    pub(crate) fn report(&self, value: &Value) {
+        if let Some(reporter) = &*self.reporter.read() {
+            reporter(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{Derived, Event, Observable, Readable, Writable, as_emitter};
+
+    use super::*;
+
+    #[test]
+    fn it_reports_named_stores_to_hook() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        set_global_hook({
+            let events = events.clone();
+            move |name, value| {
+                if name.starts_with("devtools_") {
+                    events.lock().unwrap().push(format!("{name}: {value:?}"));
+                }
+            }
+        });
+
+        let observable = Observable::new(1);
+        let doubled = Derived::new(&[as_emitter(&observable)], {
+            let observable = observable.clone();
+            move || observable.get() * 2
+        });
+        let event = Event::new();
+        let unnamed = Observable::new(0);
+
+        observable.name("devtools_observable");
+        doubled.name("devtools_doubled");
+        event.name("devtools_event");
+
+        observable.set(2);
+        event.dispatch();
+        unnamed.set(1);
+
+        clear_global_hook();
+        observable.set(3);
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                "devtools_observable: 2",
+                "devtools_doubled: 4",
+                "devtools_event: ()"
+            ]
+        );
+    }
+}
@@ -1,14 +1,12 @@
-use std::{
-    collections::HashMap,
-    sync::{Arc, RwLock},
-};
+use alloc::{boxed::Box, sync::Arc};
+use core::sync::atomic::{AtomicUsize, Ordering};
 
-use crate::Emitter;
+use crate::{Emitter, Subscribers, Unsubscribe};
 
 /// A simple observable that holds no value.
 pub struct Event {
-    callbacks: RwLock<HashMap<usize, Box<dyn Fn() + Send + Sync>>>,
-    counter: RwLock<usize>,
+    subscribers: Subscribers<()>,
+    dispatches: AtomicUsize,
 }
 
 impl Event {
@@ -24,11 +22,26 @@ impl Event {
     /// ```
     pub fn new() -> Arc<Self> {
         Arc::new(Self {
-            callbacks: RwLock::new(HashMap::new()),
-            counter: RwLock::new(0),
+            subscribers: Subscribers::new(),
+            dispatches: AtomicUsize::new(0),
         })
     }
 
+    /// Creates a new Event.
+    ///
+    /// Equivalent to `Event::new()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Event;
+    /// let event = Event::default();
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn default() -> Arc<Self> {
+        Self::new()
+    }
+
     /// Runs all registered callbacks.
     ///
     /// # Example
@@ -39,23 +52,87 @@ impl Event {
     /// event.dispatch();
     /// ```
     pub fn dispatch(&self) {
-        for callback in self.callbacks.read().unwrap().values() {
-            callback();
-        }
+        self.dispatches.fetch_add(1, Ordering::Relaxed);
+        self.subscribers.notify(&());
     }
-}
 
-impl Emitter for Event {
-    fn listen(&self, callback: impl Fn() + Send + Sync + 'static) -> impl Fn() {
-        let callback = Box::new(callback);
-        let id = *self.counter.read().unwrap();
-        *self.counter.write().unwrap() += 1;
+    /// Names the store, so its changes are reported to the global hook.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Event;
+    /// let event = Event::new();
+    /// event.name("event");
+    /// ```
+    #[cfg(feature = "debug")]
+    pub fn name(&self, name: &str) {
+        self.subscribers.name(name);
+    }
 
-        self.callbacks.write().unwrap().insert(id, callback);
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Event};
+    /// let event = Event::new();
+    /// assert_eq!(event.subscriber_count(), 0);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count()
+    }
+
+    /// Returns the number of currently registered listeners.
+    ///
+    /// Equivalent to `subscriber_count`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Emitter, Event};
+    /// let event = Event::new();
+    /// let _ = event.listen(|| {});
+    /// assert_eq!(event.listener_count(), 1);
+    /// ```
+    pub fn listener_count(&self) -> usize {
+        self.subscriber_count()
+    }
 
-        move || {
-            self.callbacks.write().unwrap().remove(&id);
-        }
+    /// Returns how many times the event has been dispatched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Event;
+    /// let event = Event::new();
+    /// event.dispatch();
+    /// assert_eq!(event.dispatch_count(), 1);
+    /// ```
+    pub fn dispatch_count(&self) -> usize {
+        self.dispatches.load(Ordering::Relaxed)
+    }
+
+    /// Removes all registered listeners.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Emitter, Event};
+    /// let event = Event::new();
+    /// let _ = event.listen(|| {});
+    ///
+    /// event.clear();
+    /// assert_eq!(event.listener_count(), 0);
+    /// ```
+    pub fn clear(&self) {
+        self.subscribers.clear();
+    }
+}
+
+impl Emitter for Event {
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.subscribers.listen(callback)
     }
 }
 
@@ -136,4 +213,58 @@ mod tests {
 
         assert_eq!(*counter.lock().unwrap(), 10);
     }
+
+    #[test]
+    fn it_counts_subscribers() {
+        let event = Event::new();
+        assert_eq!(event.subscriber_count(), 0);
+
+        let unsubscribe_a = event.listen(|| {});
+        let unsubscribe_b = event.listen(|| {});
+        assert_eq!(event.subscriber_count(), 2);
+
+        unsubscribe_a();
+        assert_eq!(event.subscriber_count(), 1);
+
+        unsubscribe_b();
+        assert_eq!(event.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_provides_default() {
+        let event = Event::default();
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = event.listen({
+            let counter = counter.clone();
+            move || {
+                *counter.lock().unwrap() += 1;
+            }
+        });
+
+        event.dispatch();
+        assert_eq!(*counter.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn it_counts_listeners_and_dispatches() {
+        let event = Event::new();
+        let first = event.listen(|| {});
+        let _second = event.listen(|| {});
+        let _third = event.listen(|| {});
+        assert_eq!(event.listener_count(), 3);
+
+        event.dispatch();
+        event.dispatch();
+        assert_eq!(event.dispatch_count(), 2);
+
+        first();
+        assert_eq!(event.listener_count(), 2);
+
+        event.clear();
+        assert_eq!(event.listener_count(), 0);
+
+        event.dispatch();
+        assert_eq!(event.dispatch_count(), 3);
+    }
 }
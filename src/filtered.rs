@@ -0,0 +1,228 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::fmt::Debug;
+
+use crate::{Emitter, ReadRef, Readable, Subscribers, Unsubscribe, lock::RwLock};
+
+/// A readable observable value that filters the values of another readable.
+///
+/// Only source values that satisfy the predicate are taken over.
+pub struct Filtered<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    value: RwLock<Value>,
+    subscribers: Subscribers<Value>,
+}
+
+impl<Value> Filtered<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    /// Creates a new filtered value.
+    ///
+    /// The initial value is taken from the source regardless of the predicate.
+    /// Afterwards only values that satisfy the predicate are taken over.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Filtered, Readable, Writable};
+    /// let observable = Observable::new(1);
+    /// let positive = Filtered::new(&observable, |value| *value > 0);
+    ///
+    /// observable.set(-1);
+    /// assert_eq!(positive.get(), 1);
+    /// ```
+    pub fn new(
+        source: &Arc<impl Readable<Value> + Send + Sync + 'static>,
+        predicate: impl Fn(&Value) -> bool + Send + Sync + 'static,
+    ) -> Arc<Self> {
+        let instance = Arc::new(Self {
+            value: RwLock::new(source.get()),
+            subscribers: Subscribers::new(),
+        });
+
+        let unsubscribe = source.subscribe({
+            let instance = Arc::downgrade(&instance);
+            move |value| {
+                if let Some(instance) = instance.upgrade()
+                    && predicate(value)
+                {
+                    *instance.value.write() = value.clone();
+                    instance.notify();
+                }
+            }
+        });
+        instance.subscribers.hold(unsubscribe);
+        instance.subscribers.keep(source.clone());
+
+        instance
+    }
+
+    /// Names the store, so its changes are reported to the global hook.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Filtered};
+    /// let filtered = Filtered::new(&Observable::new(1), |value| *value > 0);
+    /// filtered.name("filtered");
+    /// ```
+    #[cfg(feature = "debug")]
+    pub fn name(&self, name: &str)
+    where
+        Value: Debug,
+    {
+        self.subscribers.name(name);
+    }
+
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Filtered};
+    /// let observable = Observable::new(1);
+    /// let positive = Filtered::new(&observable, |value| *value > 0);
+    /// assert_eq!(positive.subscriber_count(), 0);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count()
+    }
+
+    /// Subscribes to value changes on behalf of a weakly referenced owner.
+    ///
+    /// Only a weak reference to `owner` is kept, so the subscription does not keep it alive.
+    /// The callback is run immediately and on every change as long as the owner exists.
+    /// Once the owner is dropped, the subscription removes itself on the next change.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use stores::{Filtered, Observable};
+    /// let store = Filtered::new(&Observable::new(1), |value| *value > 0);
+    /// let owner = Arc::new(Mutex::new(0));
+    /// let unsubscribe = store.subscribe_weak(&owner, |owner, value| {
+    ///     *owner.lock().unwrap() = *value;
+    /// });
+    /// ```
+    pub fn subscribe_weak<Owner>(
+        &self,
+        owner: &Arc<Owner>,
+        callback: impl Fn(&Owner, &Value) + Send + Sync + 'static,
+    ) -> Unsubscribe
+    where
+        Owner: Send + Sync + 'static,
+    {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe_weak(value, owner, callback)
+    }
+
+    /// Internal function to run all registered callbacks.
+    fn notify(&self) {
+        let value = self.value.read().clone();
+        self.subscribers.notify(&value);
+    }
+}
+
+impl<Value> Emitter for Filtered<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.subscribers.listen(callback)
+    }
+}
+
+impl<Value> Readable<Value> for Filtered<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn get(&self) -> Value {
+        self.value.read().clone()
+    }
+
+    fn subscribe_boxed(&self, callback: Box<dyn Fn(&Value) + Send + Sync>) -> Unsubscribe {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe(value, callback)
+    }
+}
+
+impl<Value> ReadRef<Value> for Filtered<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    fn with<R>(&self, reader: impl FnOnce(&Value) -> R) -> R {
+        reader(&self.value.read())
+    }
+}
+
+impl<Value> Debug for Filtered<Value>
+where
+    Value: Debug + Clone + Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Filtered")
+            .field("value", &self.value.read())
+            .field("callbacks", &self.subscribers)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{Observable, Writable};
+
+    use super::*;
+
+    #[test]
+    fn it_filters() {
+        let observable = Observable::new(1);
+        let filtered = Filtered::new(&observable, |value| value % 2 == 1);
+
+        assert_eq!(filtered.get(), 1);
+
+        observable.set(2);
+        assert_eq!(filtered.get(), 1);
+
+        observable.set(3);
+        assert_eq!(filtered.get(), 3);
+    }
+
+    #[test]
+    fn it_takes_initial_value_regardless_of_predicate() {
+        let observable = Observable::new(2);
+        let filtered = Filtered::new(&observable, |value| value % 2 == 1);
+
+        assert_eq!(filtered.get(), 2);
+    }
+
+    #[test]
+    fn it_triggers_emitter_only_on_accepted_values() {
+        let observable = Observable::new(1);
+        let filtered = Filtered::new(&observable, |value| value % 2 == 1);
+        let counter = Arc::new(Mutex::new(0));
+
+        let unsubscribe = filtered.listen({
+            let counter = counter.clone();
+            move || {
+                *counter.lock().unwrap() += 1;
+            }
+        });
+
+        observable.set(2);
+        assert_eq!(counter.lock().unwrap().clone(), 0);
+
+        observable.set(3);
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+
+        unsubscribe();
+        observable.set(5);
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+    }
+}
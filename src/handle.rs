@@ -0,0 +1,173 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::fmt::Debug;
+
+use crate::{Emitter, Observable, Readable, Unsubscribe, Writable};
+
+/// A read only handle to an observable value.
+///
+/// Created by splitting an observable via `Observable::split`.
+pub struct ReadHandle<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    target: Arc<Observable<Value>>,
+}
+
+/// A write only handle to an observable value.
+///
+/// Created by splitting an observable via `Observable::split`.
+pub struct WriteHandle<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    target: Arc<Observable<Value>>,
+}
+
+impl<Value> Observable<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    /// Splits the observable into separate read and write handles.
+    ///
+    /// Both handles refer to the same value, so changes made through the write handle are seen by
+    /// the read handle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable, Writable};
+    /// let (reader, writer) = Observable::new(1).split();
+    ///
+    /// writer.set(2);
+    /// assert_eq!(reader.get(), 2);
+    /// ```
+    ///
+    /// The read handle cannot be written to:
+    ///
+    /// ```compile_fail
+    /// use stores::{Observable, Writable};
+    /// let (reader, _) = Observable::new(1).split();
+    /// reader.set(2);
+    /// ```
+    pub fn split(self: &Arc<Self>) -> (ReadHandle<Value>, WriteHandle<Value>) {
+        (
+            ReadHandle {
+                target: self.clone(),
+            },
+            WriteHandle {
+                target: self.clone(),
+            },
+        )
+    }
+}
+
+impl<Value> Clone for ReadHandle<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    fn clone(&self) -> Self {
+        Self {
+            target: self.target.clone(),
+        }
+    }
+}
+
+impl<Value> Emitter for ReadHandle<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.target.listen_boxed(callback)
+    }
+}
+
+impl<Value> Readable<Value> for ReadHandle<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn get(&self) -> Value {
+        self.target.get()
+    }
+
+    fn subscribe_boxed(&self, callback: Box<dyn Fn(&Value) + Send + Sync>) -> Unsubscribe {
+        self.target.subscribe_boxed(callback)
+    }
+}
+
+impl<Value> Debug for ReadHandle<Value>
+where
+    Value: Debug + Clone + Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ReadHandle").field(&self.target).finish()
+    }
+}
+
+impl<Value> Clone for WriteHandle<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    fn clone(&self) -> Self {
+        Self {
+            target: self.target.clone(),
+        }
+    }
+}
+
+impl<Value> Writable<Value> for WriteHandle<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn set(&self, value: Value) {
+        self.target.set(value);
+    }
+
+    fn update_boxed(&self, updater: Box<dyn Fn(&Value) -> Value + Send + Sync>) {
+        self.target.update_boxed(updater);
+    }
+}
+
+impl<Value> Debug for WriteHandle<Value>
+where
+    Value: Debug + Clone + Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("WriteHandle").field(&self.target).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[test]
+    fn it_shares_value_between_handles() {
+        let observable = Observable::new(1);
+        let (reader, writer) = observable.split();
+
+        writer.set(2);
+        assert_eq!(reader.get(), 2);
+        assert_eq!(observable.get(), 2);
+
+        observable.set(3);
+        assert_eq!(reader.get(), 3);
+    }
+
+    #[test]
+    fn it_notifies_read_handle_of_writes() {
+        let (reader, writer) = Observable::new(1).split();
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = reader.subscribe({
+            let counter = counter.clone();
+            move |value| {
+                *counter.lock().unwrap() = *value;
+            }
+        });
+
+        writer.update(|value| value + 1);
+        assert_eq!(counter.lock().unwrap().clone(), 2);
+    }
+}
@@ -1,21 +1,222 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "std")]
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::{Mutex, mpsc::Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{lock::RwLock, subscribers::Subscribers};
+
+#[cfg(feature = "tokio")]
+mod async_derived;
+#[cfg(feature = "arc-swap")]
+mod atomic;
+#[cfg(feature = "std")]
+mod batch;
+mod bi_derived;
+mod by_id;
+#[cfg(feature = "std")]
+mod changes;
 mod deduped;
+#[cfg(feature = "std")]
+mod depth;
 mod derived;
+#[cfg(feature = "debug")]
+mod devtools;
 mod event;
+mod filtered;
+mod handle;
+mod loadable;
+mod lock;
+mod mapped;
+mod merged;
 mod observable;
+mod readonly;
+#[cfg(feature = "std")]
+mod registry;
+mod sampled;
+mod scan;
+mod skip;
+mod stats;
+mod subscribers;
+mod switch;
+mod take;
+#[cfg(feature = "std")]
+mod transaction;
+mod try_derived;
+mod unsubscribe;
 mod utils;
+mod weak;
+mod windowed;
 
+#[cfg(feature = "tokio")]
+pub use async_derived::AsyncDerived;
+#[cfg(feature = "arc-swap")]
+pub use atomic::AtomicObservable;
+#[cfg(feature = "std")]
+pub use batch::batch;
+pub use bi_derived::BiDerived;
+pub use by_id::ById;
+#[cfg(feature = "std")]
+pub use changes::Changes;
 pub use deduped::Deduped;
+#[cfg(feature = "std")]
+pub use depth::{Cycle, clear_cycle_hook, set_cycle_hook, set_recursion_limit};
 pub use derived::Derived;
+#[cfg(feature = "debug")]
+pub use devtools::{clear_global_hook, set_global_hook};
 pub use event::Event;
-pub use observable::Observable;
+pub use filtered::Filtered;
+pub use handle::{ReadHandle, WriteHandle};
+pub use loadable::Loadable;
+pub use mapped::Mapped;
+pub use merged::Merged;
+pub use observable::{ModifyGuard, Observable, ReadGuard};
+pub use readonly::ReadOnly;
+#[cfg(feature = "std")]
+pub use registry::Registry;
+pub use sampled::Sampled;
+pub use scan::Scan;
+pub use skip::Skip;
+pub use stats::RunningStats;
+pub use switch::Switch;
+pub use take::Take;
+#[cfg(feature = "std")]
+pub use transaction::transaction;
+pub use try_derived::TryDerived;
+pub use unsubscribe::{PausableSubscription, Subscription, Unsubscribe};
+pub use weak::WeakObservable;
+pub use windowed::Windowed;
+
+/// Registered callbacks of a store by priority and id, shared with the unsubscribe handles.
+///
+/// Callbacks run in ascending priority, those with equal priority in order of registration.
+type Callbacks<Value> = Arc<RwLock<BTreeMap<(i32, usize), Arc<Callback<Value>>>>>;
 
 /// Enum to differentiate between Emitter and Readable subscriptions.
 enum Callback<Value>
 where
-    Value: Clone + Send + Sync,
+    Value: Send + Sync,
 {
     Subscriber(Box<dyn Fn(&Value) + Send + Sync>),
     Listener(Box<dyn Fn() + Send + Sync>),
+    Weak(Box<dyn Fn(&Value) -> bool + Send + Sync>),
+    #[cfg(feature = "std")]
+    Channel(Sender<Value>),
+    #[cfg(feature = "tokio")]
+    Broadcast(tokio::sync::broadcast::Sender<Value>),
+}
+
+impl<Value> Callback<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    /// Runs the callback with the given value.
+    ///
+    /// Returns `false` if the callback is no longer able to receive values.
+    fn call(&self, value: &Value) -> bool {
+        match self {
+            Callback::Subscriber(func) => func(value),
+            Callback::Listener(func) => func(),
+            Callback::Weak(func) => return func(value),
+            #[cfg(feature = "std")]
+            Callback::Channel(sender) => return sender.send(value.clone()).is_ok(),
+            #[cfg(feature = "tokio")]
+            Callback::Broadcast(sender) => return sender.send(value.clone()).is_ok(),
+        }
+        true
+    }
+
+    /// Runs all callbacks with the given value.
+    ///
+    /// The callbacks are run on a snapshot, so they are free to subscribe or unsubscribe.
+    /// Callbacks that are no longer able to receive values are removed afterwards.
+    fn notify_all(callbacks: &Callbacks<Value>, value: &Value) {
+        Self::notify_with(callbacks, |_, callback| callback.call(value));
+    }
+
+    /// Runs all callbacks of an observable that are not skipped, tracking which one is running.
+    ///
+    /// Allows changes made by a callback to its own observable to be attributed to it.
+    #[cfg(feature = "std")]
+    fn notify_tracked(
+        callbacks: &Callbacks<Value>,
+        value: &Value,
+        store: usize,
+        skipped: &[(i32, usize)],
+    ) {
+        Self::notify_with(callbacks, |id, callback| {
+            skipped.contains(id) || depth::running(store, *id, || callback.call(value))
+        });
+    }
+
+    /// Internal function to run all callbacks through `call`, which returns `false` to remove one.
+    fn notify_with(
+        callbacks: &Callbacks<Value>,
+        call: impl Fn(&(i32, usize), &Callback<Value>) -> bool,
+    ) {
+        let snapshot: Vec<_> = callbacks
+            .read()
+            .iter()
+            .map(|(id, callback)| (*id, callback.clone()))
+            .collect();
+
+        let closed: Vec<_> = snapshot
+            .into_iter()
+            .filter(|(id, callback)| {
+                #[cfg(feature = "std")]
+                let kept = depth::calling(|| call(id, callback));
+                #[cfg(not(feature = "std"))]
+                let kept = call(id, callback);
+                !kept
+            })
+            .map(|(id, _)| id)
+            .collect();
+
+        if !closed.is_empty() {
+            let mut callbacks = callbacks.write();
+            for id in closed {
+                callbacks.remove(&id);
+            }
+        }
+    }
+}
+
+/// Internal function to hash a value into a fingerprint.
+#[cfg(feature = "std")]
+fn fingerprint(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Coerces a store into a target of derived values.
+///
+/// Derived values accept any mix of targets, which have to be passed as `Arc<dyn Emitter>`.
+/// A slice of a single target is not coerced on its own, so this takes care of it.
+///
+/// # Example
+///
+/// ```
+/// use stores::{Derived, Observable, Readable, as_emitter};
+/// let observable = Observable::new(1);
+/// let doubled = Derived::new(&[as_emitter(&observable)], {
+///     let observable = observable.clone();
+///     move || observable.get() * 2
+/// });
+/// assert_eq!(doubled.get(), 2);
+/// ```
+pub fn as_emitter(
+    store: &Arc<impl Emitter + Send + Sync + 'static>,
+) -> Arc<dyn Emitter + Send + Sync> {
+    store.clone()
 }
 
 /// Contract used to subscribe to changes.
@@ -24,7 +225,7 @@ pub trait Emitter {
     ///
     /// Registers a callback that is run whenever there are internal changes.
     /// The callback will not be run until the first change.
-    /// It returns a function that can be used to unsubscribe.
+    /// It returns a handle that can be called to unsubscribe.
     ///
     /// # Example
     ///
@@ -33,7 +234,110 @@ pub trait Emitter {
     /// # let observable = Observable::new(0);
     /// let unsubscribe = observable.listen(|| println!("Change detected"));
     /// ```
-    fn listen(&self, callback: impl Fn() + Send + Sync + 'static) -> impl Fn();
+    fn listen(&self, callback: impl Fn() + Send + Sync + 'static) -> Unsubscribe
+    where
+        Self: Sized,
+    {
+        self.listen_boxed(Box::new(callback))
+    }
+
+    /// Subscribe to internal changes until the returned guard is dropped.
+    ///
+    /// Behaves like `listen`, but unsubscribes automatically.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Emitter};
+    /// # let observable = Observable::new(0);
+    /// {
+    ///     let _subscription = observable.listen_guard(|| println!("Change detected"));
+    ///     assert_eq!(observable.subscriber_count(), 1);
+    /// }
+    /// assert_eq!(observable.subscriber_count(), 0);
+    /// ```
+    fn listen_guard(&self, callback: impl Fn() + Send + Sync + 'static) -> Subscription
+    where
+        Self: Sized,
+    {
+        self.listen(callback).into()
+    }
+
+    /// Subscribe to internal changes with a callback that can be paused and resumed.
+    ///
+    /// While paused, changes are skipped without removing the callback,
+    /// so it keeps its position among the other callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Event, Emitter};
+    /// # let event = Event::new();
+    /// let subscription = event.listen_pausable(|| println!("Change detected"));
+    /// subscription.pause();
+    /// event.dispatch(); // prints nothing
+    /// ```
+    fn listen_pausable(&self, callback: impl Fn() + Send + Sync + 'static) -> PausableSubscription
+    where
+        Self: Sized,
+    {
+        let paused = Arc::new(AtomicBool::new(false));
+        let unsubscribe = self.listen({
+            let paused = paused.clone();
+            move || {
+                if !paused.load(Ordering::Acquire) {
+                    callback();
+                }
+            }
+        });
+
+        PausableSubscription::new(paused, unsubscribe)
+    }
+
+    /// Subscribe to internal changes with a callback that mutates its captured state.
+    ///
+    /// Behaves like `listen`, but the callback is kept behind a lock and run exclusively.
+    /// The callback must therefore not trigger itself again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Emitter};
+    /// # let observable = Observable::new(0);
+    /// let mut changes = 0;
+    /// let unsubscribe = observable.listen_mut(move || changes += 1);
+    /// ```
+    fn listen_mut(&self, callback: impl FnMut() + Send + 'static) -> Unsubscribe
+    where
+        Self: Sized,
+    {
+        let callback = lock::Mutex::new(callback);
+        self.listen(move || (callback.lock())())
+    }
+
+    /// Subscribe to internal changes with a boxed callback.
+    ///
+    /// Behaves like `listen`, but can also be called on `dyn Emitter`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use stores::{Observable, Emitter};
+    /// let emitter: Arc<dyn Emitter> = Observable::new(0);
+    /// let unsubscribe = emitter.listen_boxed(Box::new(|| println!("Change detected")));
+    /// ```
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe;
+}
+
+/// Shared stores are emitters as well, so they can be boxed as `dyn Emitter`.
+impl<Target> Emitter for Arc<Target>
+where
+    Target: Emitter + ?Sized,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        (**self).listen_boxed(callback)
+    }
 }
 
 /// Contract for reading and subscribing to values.
@@ -52,11 +356,31 @@ where
     /// ```
     fn get(&self) -> Value;
 
+    /// Takes a snapshot of the current value.
+    ///
+    /// Behaves like `get`, but signals that the value is captured for later comparison, e.g. in
+    /// tests.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable, Writable};
+    /// # let observable = Observable::new(1);
+    /// let before = observable.snapshot();
+    /// observable.set(2);
+    /// assert_ne!(before, observable.snapshot());
+    /// ```
+    fn snapshot(&self) -> Value {
+        self.get()
+    }
+
     /// Subscribe to any value changes.
     ///
     /// Registers a callback that is run whenever the internal value changes.
     /// The callback will also be run once immediately.
-    /// It returns a function that can be used to unsubscribe.
+    /// Callbacks run in the order they were registered, `Observable::subscribe_priority`
+    /// allows running some of them earlier or later.
+    /// It returns a handle that can be called to unsubscribe.
     ///
     /// # Example
     ///
@@ -65,7 +389,683 @@ where
     /// # let observable = Observable::new(1);
     /// let unsubscribe = observable.subscribe(|value| println!("{}", value));
     /// ```
-    fn subscribe(&self, callback: impl Fn(&Value) + Send + Sync + 'static) -> impl Fn();
+    fn subscribe(&self, callback: impl Fn(&Value) + Send + Sync + 'static) -> Unsubscribe
+    where
+        Self: Sized,
+    {
+        self.subscribe_boxed(Box::new(callback))
+    }
+
+    /// Subscribe to any value changes with a boxed callback.
+    ///
+    /// Behaves like `subscribe`, but can also be called on `dyn Readable`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use stores::{Observable, Readable};
+    /// let readable: Arc<dyn Readable<i32>> = Observable::new(1);
+    /// let unsubscribe = readable.subscribe_boxed(Box::new(|value| println!("{}", value)));
+    /// ```
+    fn subscribe_boxed(&self, callback: Box<dyn Fn(&Value) + Send + Sync>) -> Unsubscribe;
+
+    /// Erases the concrete type, so different readables can be stored together.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use stores::{Observable, Readable};
+    /// let a = Observable::new(1);
+    /// let doubled = a.map(|value| value * 2);
+    /// let readables: Vec<Arc<dyn Readable<i32> + Send + Sync>> =
+    ///     vec![a.into_arc_dyn(), doubled.into_arc_dyn()];
+    /// ```
+    fn into_arc_dyn(self: Arc<Self>) -> Arc<dyn Readable<Value> + Send + Sync>
+    where
+        Self: Send + Sync + Sized + 'static,
+        Value: 'static,
+    {
+        self
+    }
+
+    /// Subscribe to value changes until the returned guard is dropped.
+    ///
+    /// Behaves like `subscribe`, but unsubscribes automatically.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable};
+    /// # let observable = Observable::new(1);
+    /// let subscription = observable.subscribe_guard(|value| println!("{}", value));
+    /// drop(subscription);
+    /// ```
+    fn subscribe_guard(&self, callback: impl Fn(&Value) + Send + Sync + 'static) -> Subscription
+    where
+        Self: Sized,
+    {
+        self.subscribe(callback).into()
+    }
+
+    /// Subscribe to value changes with a callback that mutates its captured state.
+    ///
+    /// Behaves like `subscribe`, but the callback is kept behind a lock and run exclusively.
+    /// The callback must therefore not trigger itself again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable};
+    /// # let observable = Observable::new(1);
+    /// let mut sum = 0;
+    /// let unsubscribe = observable.subscribe_mut(move |value| sum += value);
+    /// ```
+    fn subscribe_mut(&self, callback: impl FnMut(&Value) + Send + 'static) -> Unsubscribe
+    where
+        Self: Sized,
+    {
+        let callback = lock::Mutex::new(callback);
+        self.subscribe(move |value| (callback.lock())(value))
+    }
+
+    /// Subscribe to value changes without the immediate call.
+    ///
+    /// Like `subscribe`, but the callback is only run for values emitted afterwards.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable};
+    /// # let observable = Observable::new(1);
+    /// let unsubscribe = observable.on_change(|value| println!("{}", value));
+    /// ```
+    fn on_change(&self, callback: impl Fn(&Value) + Send + Sync + 'static) -> Unsubscribe
+    where
+        Self: Sized,
+    {
+        let initial = AtomicBool::new(true);
+        self.subscribe(move |value| {
+            if !initial.swap(false, Ordering::AcqRel) {
+                callback(value);
+            }
+        })
+    }
+
+    /// Subscribe to value changes that satisfy a predicate.
+    ///
+    /// Like `subscribe`, but the callback is only run for values the predicate accepts.
+    /// This includes the immediate call, which is skipped if the current value is rejected.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable};
+    /// # let observable = Observable::new(1);
+    /// let unsubscribe = observable.subscribe_filtered(
+    ///     |value| value % 2 == 0,
+    ///     |value| println!("{} is even", value),
+    /// );
+    /// ```
+    fn subscribe_filtered(
+        &self,
+        predicate: impl Fn(&Value) -> bool + Send + Sync + 'static,
+        callback: impl Fn(&Value) + Send + Sync + 'static,
+    ) -> Unsubscribe
+    where
+        Self: Sized,
+    {
+        self.subscribe(move |value| {
+            if predicate(value) {
+                callback(value);
+            }
+        })
+    }
+
+    /// Subscribe to value changes, skipping values with the same key as the previous one.
+    ///
+    /// The callback is run immediately and afterwards only when the key extracted from the new
+    /// value differs from the key of the previous value.
+    /// Allows deduplicating values that do not implement `PartialEq` themselves.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable};
+    /// # let observable = Observable::new((1, "first"));
+    /// let unsubscribe = observable.subscribe_distinct_by(
+    ///     |value| value.0,
+    ///     |value| println!("{}", value.1),
+    /// );
+    /// ```
+    fn subscribe_distinct_by<Key>(
+        &self,
+        key: impl Fn(&Value) -> Key + Send + Sync + 'static,
+        callback: impl Fn(&Value) + Send + Sync + 'static,
+    ) -> Unsubscribe
+    where
+        Self: Sized,
+        Key: PartialEq + Send + 'static,
+    {
+        let previous = lock::Mutex::new(None);
+        self.subscribe(move |value| {
+            let current = Some(key(value));
+            let changed = {
+                let mut previous = previous.lock();
+                let changed = *previous != current;
+                *previous = current;
+                changed
+            };
+
+            if changed {
+                callback(value);
+            }
+        })
+    }
+
+    /// Subscribe to value changes, skipping values equal to the one this callback saw last.
+    ///
+    /// Unlike `Deduped`, which compares against the last value of the store, every subscription
+    /// keeps its own baseline, starting with the value of the immediate call.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable};
+    /// # let observable = Observable::new(1);
+    /// let unsubscribe = observable.subscribe_deduped(|value| println!("{}", value));
+    /// ```
+    fn subscribe_deduped(&self, callback: impl Fn(&Value) + Send + Sync + 'static) -> Unsubscribe
+    where
+        Self: Sized,
+        Value: PartialEq + 'static,
+    {
+        self.subscribe_distinct_by(Value::clone, callback)
+    }
+
+    /// Subscribe to value changes, running the callback at most once per interval.
+    ///
+    /// The callback is run immediately and afterwards only if the interval elapsed since its last
+    /// call. Values arriving earlier are not lost: the latest one is delivered on a background
+    /// thread once the interval has elapsed. Other subscribers are not affected.
+    /// It returns a handle that can be called to unsubscribe, which also cancels pending values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use stores::{Observable, Readable};
+    /// # let observable = Observable::new(1);
+    /// let unsubscribe = observable.subscribe_throttled(
+    ///     Duration::from_millis(100),
+    ///     |value| println!("{}", value),
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    fn subscribe_throttled(
+        &self,
+        interval: Duration,
+        callback: impl Fn(&Value) + Send + Sync + 'static,
+    ) -> Unsubscribe
+    where
+        Self: Sized,
+        Value: 'static,
+    {
+        let callback = Arc::new(callback);
+        let active = Arc::new(AtomicBool::new(true));
+        // Time of the last call and the latest value waiting for delivery.
+        let state = Arc::new(Mutex::new((None::<Instant>, None::<Value>)));
+
+        let unsubscribe = self.subscribe({
+            let active = active.clone();
+            move |value| {
+                let mut guard = state.lock().unwrap();
+                let (last, pending) = &mut *guard;
+                let now = Instant::now();
+                let remaining = last.map_or(Duration::ZERO, |last| {
+                    interval.saturating_sub(now.duration_since(last))
+                });
+
+                if remaining.is_zero() {
+                    *last = Some(now);
+                    *pending = None;
+                    drop(guard);
+                    callback(value);
+                    return;
+                }
+
+                if pending.replace(value.clone()).is_some() {
+                    return;
+                }
+
+                thread::spawn({
+                    let state = state.clone();
+                    let callback = callback.clone();
+                    let active = active.clone();
+                    move || {
+                        thread::sleep(remaining);
+                        let value = {
+                            let mut guard = state.lock().unwrap();
+                            guard.0 = Some(Instant::now());
+                            guard.1.take()
+                        };
+
+                        if let Some(value) = value
+                            && active.load(Ordering::Acquire)
+                        {
+                            callback(&value);
+                        }
+                    }
+                });
+            }
+        });
+
+        Unsubscribe::new(move || {
+            active.store(false, Ordering::Release);
+            unsubscribe();
+        })
+    }
+
+    /// Writes every value into another writable.
+    ///
+    /// The destination is updated immediately with the current value and on every change
+    /// afterwards. The destination is kept alive until the pipe is removed.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable, Writable};
+    /// # let observable = Observable::new(1);
+    /// let destination = Observable::new(0);
+    /// let unsubscribe = observable.pipe_to(&destination);
+    /// assert_eq!(destination.get(), 1);
+    /// ```
+    fn pipe_to(
+        &self,
+        destination: &Arc<impl Writable<Value> + Send + Sync + 'static>,
+    ) -> Unsubscribe
+    where
+        Self: Sized,
+    {
+        let destination = destination.clone();
+        self.subscribe(move |value| destination.set(value.clone()))
+    }
+
+    /// Creates a new readable that maps every value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable};
+    /// # let observable = Observable::new(1);
+    /// let doubled = observable.map(|value| value * 2);
+    /// ```
+    fn map<Out>(
+        self: &Arc<Self>,
+        map: impl Fn(&Value) -> Out + Send + Sync + 'static,
+    ) -> Arc<Mapped<Out>>
+    where
+        Self: Sized + Send + Sync + 'static,
+        Out: Clone + Send + Sync + 'static,
+    {
+        Mapped::new(self, map)
+    }
+
+    /// Creates a new readable that maps every value, reading it by reference.
+    ///
+    /// Like `map`, but the current value is not cloned to compute the initial result.
+    /// Combined with `with` on the result, this allows reading small parts of large values
+    /// without cloning them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, ReadRef, Readable};
+    /// # let observable = Observable::new(vec![1, 2, 3]);
+    /// let first = observable.map_ref(|value| value[0]);
+    /// assert_eq!(first.with(|first| *first), 1);
+    /// ```
+    fn map_ref<Out>(
+        self: &Arc<Self>,
+        map: impl Fn(&Value) -> Out + Send + Sync + 'static,
+    ) -> Arc<Mapped<Out>>
+    where
+        Self: ReadRef<Value> + Emitter + Send + Sync + Sized + 'static,
+        Out: Clone + Send + Sync + 'static,
+    {
+        Mapped::from_ref(self, map)
+    }
+
+    /// Creates a new derived value combining this and another readable.
+    ///
+    /// The combine function receives the current values of both readables and is run whenever
+    /// one of them changes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable};
+    /// let a = Observable::new(1);
+    /// let b = Observable::new(2);
+    /// let sum = a.zip_with(&b, |a, b| a + b);
+    /// assert_eq!(sum.get(), 3);
+    /// ```
+    fn zip_with<Other, Out>(
+        self: &Arc<Self>,
+        other: &Arc<impl Readable<Other> + Emitter + Send + Sync + 'static>,
+        combine: impl Fn(&Value, &Other) -> Out + Send + Sync + 'static,
+    ) -> Arc<Derived<Out>>
+    where
+        Self: Emitter + Send + Sync + Sized + 'static,
+        Other: Clone + Send + Sync,
+        Out: Clone + Send + Sync + 'static,
+    {
+        Derived::new(&[self.clone(), other.clone()], {
+            let a = self.clone();
+            let b = other.clone();
+            move || combine(&a.get(), &b.get())
+        })
+    }
+
+    /// Creates a new readable that projects a part of every value, e.g. a struct field.
+    ///
+    /// This is an alias of `map` for the common case of exposing single fields
+    /// of a larger value as their own readables.
+    /// Combine it with `dedup` to only get notified when the field itself changes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use stores::{Observable, Readable};
+    /// struct Config {
+    ///     timeout: u64,
+    ///     retries: u8,
+    /// }
+    ///
+    /// let config = Observable::new(Arc::new(Config { timeout: 30, retries: 3 }));
+    /// let timeout = config.project(|config| config.timeout);
+    /// let retries = config.project(|config| config.retries).dedup();
+    /// ```
+    fn project<Field>(
+        self: &Arc<Self>,
+        project: impl Fn(&Value) -> Field + Send + Sync + 'static,
+    ) -> Arc<Mapped<Field>>
+    where
+        Self: Sized + Send + Sync + 'static,
+        Field: Clone + Send + Sync + 'static,
+    {
+        Mapped::new(self, project)
+    }
+
+    /// Creates a new readable that only takes over values matching the predicate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable};
+    /// # let observable = Observable::new(1);
+    /// let positive = observable.filter(|value| *value > 0);
+    /// ```
+    fn filter(
+        self: &Arc<Self>,
+        predicate: impl Fn(&Value) -> bool + Send + Sync + 'static,
+    ) -> Arc<Filtered<Value>>
+    where
+        Self: Sized + Send + Sync + 'static,
+        Value: 'static,
+    {
+        Filtered::new(self, predicate)
+    }
+
+    /// Creates a new readable that ignores the first `count` changes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable};
+    /// # let observable = Observable::new(1);
+    /// let skipped = observable.skip(2);
+    /// ```
+    fn skip(self: &Arc<Self>, count: usize) -> Arc<Skip<Value>>
+    where
+        Self: Sized + Send + Sync + 'static,
+        Value: 'static,
+    {
+        Skip::new(self, count)
+    }
+
+    /// Creates a new readable that ignores all changes until the trigger fires.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Event, Observable, Readable};
+    /// # let observable = Observable::new(1);
+    /// let trigger = Event::new();
+    /// let skipped = observable.skip_until(&*trigger);
+    /// ```
+    fn skip_until(self: &Arc<Self>, trigger: &(impl Emitter + ?Sized)) -> Arc<Skip<Value>>
+    where
+        Self: Sized + Send + Sync + 'static,
+        Value: 'static,
+    {
+        Skip::until(self, trigger)
+    }
+
+    /// Creates a new readable that only notifies when the value has actually changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable};
+    /// # let observable = Observable::new(1);
+    /// let deduped = observable.map(|value| value / 2).dedup();
+    /// ```
+    fn dedup(self: &Arc<Self>) -> Arc<Deduped<Value, Self>>
+    where
+        Self: Sized + Emitter + Send + Sync + 'static,
+        Value: PartialEq + Eq + 'static,
+    {
+        Deduped::from(self.clone())
+    }
+
+    /// Creates a new readable that maps every value and only notifies when the result changes.
+    ///
+    /// Shorthand for `map` followed by `dedup`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable};
+    /// # let observable = Observable::new(1);
+    /// let halved = observable.map_dedup(|value| value / 2);
+    /// ```
+    fn map_dedup<Out>(
+        self: &Arc<Self>,
+        map: impl Fn(&Value) -> Out + Send + Sync + 'static,
+    ) -> Arc<Deduped<Out, Mapped<Out>>>
+    where
+        Self: Sized + Send + Sync + 'static,
+        Out: PartialEq + Eq + Clone + Send + Sync + 'static,
+    {
+        self.map(map).dedup()
+    }
+
+    /// Returns a fingerprint of the current value.
+    ///
+    /// Values that are expensive to compare but cheap to hash can be checked for changes by
+    /// comparing fingerprints. Different values can produce the same fingerprint, so an unchanged
+    /// fingerprint only means that the value is very likely unchanged.
+    /// Fingerprints are only stable within the same process.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable, Writable};
+    /// # let observable = Observable::new(1);
+    /// let fingerprint = observable.fingerprint();
+    /// observable.set(2);
+    /// assert_ne!(observable.fingerprint(), fingerprint);
+    /// ```
+    #[cfg(feature = "std")]
+    fn fingerprint(&self) -> u64
+    where
+        Self: Sized,
+        Value: Hash,
+    {
+        fingerprint(&self.get())
+    }
+
+    /// Creates a blocking iterator over all future values.
+    ///
+    /// The current value is not yielded, only the values emitted afterwards.
+    /// The iterator never ends on its own, it blocks until the next value is emitted.
+    /// Dropping the iterator removes its subscription.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::thread;
+    /// # use stores::{Observable, Readable, Writable};
+    /// # let observable = Observable::new(1);
+    /// let mut changes = observable.changes();
+    /// thread::spawn({
+    ///     let observable = observable.clone();
+    ///     move || observable.set(2)
+    /// });
+    /// assert_eq!(changes.next(), Some(2));
+    /// ```
+    #[cfg(feature = "std")]
+    fn changes(&self) -> Changes<Value>
+    where
+        Self: Sized,
+        Value: 'static,
+    {
+        Changes::new(self)
+    }
+
+    /// Blocks the current thread until the value satisfies the predicate.
+    ///
+    /// Returns the first matching value, which may be the current one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable};
+    /// # let observable = Observable::new(1);
+    /// let value = observable.wait_for(|value| *value > 0);
+    /// ```
+    #[cfg(feature = "std")]
+    fn wait_for(&self, predicate: impl Fn(&Value) -> bool) -> Value
+    where
+        Self: Sized,
+        Value: 'static,
+    {
+        wait_until(self, predicate, None).unwrap()
+    }
+
+    /// Blocks the current thread until the value satisfies the predicate or the timeout elapses.
+    ///
+    /// Returns `None` if no matching value was emitted in time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use stores::{Observable, Readable};
+    /// # let observable = Observable::new(1);
+    /// let value = observable.wait_for_timeout(|value| *value > 1, Duration::from_millis(10));
+    /// assert_eq!(value, None);
+    /// ```
+    #[cfg(feature = "std")]
+    fn wait_for_timeout(
+        &self,
+        predicate: impl Fn(&Value) -> bool,
+        timeout: Duration,
+    ) -> Option<Value>
+    where
+        Self: Sized,
+        Value: 'static,
+    {
+        wait_until(self, predicate, Some(Instant::now() + timeout))
+    }
+
+    /// Records every value into a shared vector, intended for tests.
+    ///
+    /// The current value is recorded immediately, followed by every subsequent change.
+    /// Returns the recorded values together with a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable, Writable};
+    /// # let observable = Observable::new(1);
+    /// let (values, unsubscribe) = observable.record();
+    /// observable.set(2);
+    /// assert_eq!(*values.lock().unwrap(), vec![1, 2]);
+    /// ```
+    #[cfg(any(feature = "testing", test))]
+    fn record(&self) -> (Arc<std::sync::Mutex<Vec<Value>>>, Unsubscribe)
+    where
+        Self: Sized,
+        Value: 'static,
+    {
+        let values = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let unsubscribe = self.subscribe({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(value.clone())
+        });
+
+        (values, unsubscribe)
+    }
+}
+
+/// Internal function to block until a matching value is emitted.
+///
+/// All emitted values are queued, so short lived matches are not missed.
+#[cfg(feature = "std")]
+fn wait_until<Value>(
+    readable: &impl Readable<Value>,
+    predicate: impl Fn(&Value) -> bool,
+    deadline: Option<Instant>,
+) -> Option<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    // Unlike for `changes`, the current value is queued as well.
+    let mut changes = Changes::queue(|push| readable.subscribe_boxed(push));
+    loop {
+        let value = changes.next_before(deadline)?;
+        if predicate(&value) {
+            return Some(value);
+        }
+    }
+}
+
+/// Contract for reading values by reference.
+///
+/// Unlike `Readable`, this does not require the value to implement `Clone`.
+pub trait ReadRef<Value> {
+    /// Reads the current value by reference.
+    ///
+    /// The value is locked while `reader` runs,
+    /// so `reader` must not write to the same store or it will deadlock.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, ReadRef};
+    /// # let observable = Observable::new(vec![1, 2, 3]);
+    /// let len = observable.with(|value| value.len());
+    /// ```
+    fn with<R>(&self, reader: impl FnOnce(&Value) -> R) -> R;
 }
 
 /// Contract for writing and updating values.
@@ -89,6 +1089,7 @@ where
     /// Updates the internal value based on its current value.
     ///
     /// Calling this will trigger all registered callbacks.
+    /// The updater may be run more than once, so concurrent updates are not lost.
     ///
     /// # Example
     ///
@@ -97,5 +1098,569 @@ where
     /// # let observable = Observable::new(0);
     /// observable.update(|value| value * 2);
     /// ```
-    fn update(&self, updater: impl Fn(&Value) -> Value + Send + Sync + 'static);
+    fn update(&self, updater: impl Fn(&Value) -> Value + Send + Sync + 'static)
+    where
+        Self: Sized,
+    {
+        self.update_boxed(Box::new(updater));
+    }
+
+    /// Updates the internal value based on its current value with a boxed updater.
+    ///
+    /// Behaves like `update`, but can also be called on `dyn Writable`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use stores::{Observable, Writable};
+    /// let writable: Arc<dyn Writable<i32>> = Observable::new(0);
+    /// writable.update_boxed(Box::new(|value| value * 2));
+    /// ```
+    fn update_boxed(&self, updater: Box<dyn Fn(&Value) -> Value + Send + Sync>);
+
+    /// Updates the internal value once based on its current value.
+    ///
+    /// Unlike `update`, the updater only has to be callable once and does not have to be `'static`,
+    /// so it can consume captured data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Writable};
+    /// # let observable = Observable::new(String::from("Hello"));
+    /// let suffix = String::from(" World");
+    /// observable.update_once(move |value| value.clone() + &suffix);
+    /// ```
+    fn update_once(&self, updater: impl FnOnce(&Value) -> Value)
+    where
+        Self: Sized + Readable<Value>,
+    {
+        self.set(updater(&self.get()));
+    }
+
+    /// Conditionally updates the internal value based on its current value.
+    ///
+    /// If the updater returns `None`, the value is left untouched and no callbacks are triggered.
+    /// Otherwise this behaves like `set`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Writable};
+    /// # let observable = Observable::new(0);
+    /// observable.update_maybe(|value| (*value < 10).then(|| value + 1));
+    /// ```
+    fn update_maybe(&self, updater: impl FnOnce(&Value) -> Option<Value>)
+    where
+        Self: Sized + Readable<Value>,
+    {
+        if let Some(value) = updater(&self.get()) {
+            self.set(value);
+        }
+    }
+
+    /// Sets the internal value to the current value of another readable.
+    ///
+    /// Calling this will trigger all registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable, Writable};
+    /// let source = Observable::new(1);
+    /// let destination = Observable::new(0);
+    /// destination.set_from(&*source);
+    /// assert_eq!(destination.get(), 1);
+    /// ```
+    fn set_from(&self, source: &impl Readable<Value>)
+    where
+        Self: Sized,
+    {
+        self.set(source.get());
+    }
+
+    /// Updates the internal value by combining it with the current value of another readable.
+    ///
+    /// Calling this will trigger all registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable, Writable};
+    /// let source = Observable::new(2);
+    /// let destination = Observable::new(1);
+    /// destination.update_from(&*source, |current, source| current + source);
+    /// assert_eq!(destination.get(), 3);
+    /// ```
+    fn update_from<Source>(
+        &self,
+        source: &impl Readable<Source>,
+        combine: impl FnOnce(&Value, &Source) -> Value,
+    ) where
+        Self: Sized + Readable<Value>,
+        Source: Clone + Send + Sync,
+    {
+        let source = source.get();
+        self.update_once(|current| combine(current, &source));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    #[cfg(feature = "std")]
+    use std::{thread, time::Duration};
+
+    use super::*;
+
+    #[test]
+    fn it_chains_combinators() {
+        let observable = Observable::new(1);
+        let pipeline = observable
+            .map(|value| value / 2)
+            .filter(|value| *value > 0)
+            .dedup();
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = pipeline.subscribe({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+
+        observable.set(4);
+        observable.set(5);
+        observable.set(-4);
+        observable.set(6);
+
+        assert_eq!(pipeline.get(), 3);
+        assert_eq!(*values.lock().unwrap(), vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn it_projects_fields() {
+        #[derive(Clone)]
+        struct Config {
+            timeout: u64,
+            retries: u8,
+        }
+
+        let config = Observable::new(Arc::new(Config {
+            timeout: 30,
+            retries: 3,
+        }));
+        let timeout = config.project(|config| config.timeout);
+        let retries = config.project(|config| config.retries).dedup();
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = retries.listen({
+            let counter = counter.clone();
+            move || *counter.lock().unwrap() += 1
+        });
+
+        config.update(|config| {
+            Arc::new(Config {
+                timeout: 60,
+                ..(**config).clone()
+            })
+        });
+        assert_eq!(timeout.get(), 60);
+        assert_eq!(retries.get(), 3);
+        assert_eq!(*counter.lock().unwrap(), 0);
+
+        config.update(|config| {
+            Arc::new(Config {
+                retries: 5,
+                ..(**config).clone()
+            })
+        });
+        assert_eq!(timeout.get(), 60);
+        assert_eq!(retries.get(), 5);
+        assert_eq!(*counter.lock().unwrap(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_waits_for_matching_value() {
+        let observable = Observable::new(0);
+
+        let writer = thread::spawn({
+            let observable = observable.clone();
+            move || {
+                thread::sleep(Duration::from_millis(20));
+                observable.set(1);
+                observable.set(2);
+            }
+        });
+
+        assert_eq!(observable.wait_for(|value| *value == 2), 2);
+        assert_eq!(observable.subscriber_count(), 0);
+        writer.join().unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_returns_current_value_if_matching() {
+        let observable = Observable::new(5);
+        assert_eq!(observable.wait_for(|value| *value == 5), 5);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_waits_for_matching_value_with_timeout() {
+        let observable = Observable::new(0);
+        assert_eq!(
+            observable.wait_for_timeout(|value| *value == 1, Duration::from_millis(10)),
+            None
+        );
+
+        let writer = thread::spawn({
+            let observable = observable.clone();
+            move || {
+                thread::sleep(Duration::from_millis(20));
+                observable.set(1);
+            }
+        });
+
+        assert_eq!(
+            observable.wait_for_timeout(|value| *value == 1, Duration::from_secs(5)),
+            Some(1)
+        );
+        writer.join().unwrap();
+    }
+
+    #[test]
+    fn it_runs_on_change_only_for_later_values() {
+        let observable = Observable::new(1);
+        let doubled = observable.map(|value| value * 2);
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let unsubscribe = doubled.on_change({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+        assert!(values.lock().unwrap().is_empty());
+
+        observable.set(2);
+        observable.set(3);
+        assert_eq!(*values.lock().unwrap(), vec![4, 6]);
+
+        unsubscribe();
+        observable.set(4);
+        assert_eq!(*values.lock().unwrap(), vec![4, 6]);
+    }
+
+    #[test]
+    fn it_maps_deduped_fields() {
+        #[derive(Clone)]
+        struct User {
+            name: &'static str,
+            visits: u32,
+        }
+
+        let user = Observable::new(User {
+            name: "Alice",
+            visits: 0,
+        });
+        let name = user.map_dedup(|user| user.name);
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = name.listen({
+            let counter = counter.clone();
+            move || *counter.lock().unwrap() += 1
+        });
+
+        user.modify(|user| user.visits += 1);
+        user.modify(|user| user.visits += 1);
+        assert_eq!(name.get(), "Alice");
+        assert_eq!(*counter.lock().unwrap(), 0);
+
+        user.modify(|user| user.name = "Bob");
+        assert_eq!(name.get(), "Bob");
+        assert_eq!(*counter.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn it_updates_once_with_moved_data() {
+        let observable = Observable::new(vec![String::from("a")]);
+        let owned = String::from("b");
+
+        observable.update_once(move |value| {
+            let mut value = value.clone();
+            value.push(owned);
+            value
+        });
+
+        assert_eq!(observable.get(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn it_runs_mutable_callbacks() {
+        let observable = Observable::new(1);
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        let mut sum = 0;
+        let _ = observable.subscribe_mut(move |value| {
+            sum += value;
+            sender.send(sum).unwrap();
+        });
+
+        observable.set(2);
+        observable.set(3);
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec![1, 3, 6]);
+    }
+
+    #[test]
+    fn it_runs_mutable_listeners() {
+        let event = Event::new();
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        let mut count = 0;
+        let _ = event.listen_mut(move || {
+            count += 1;
+            sender.send(count).unwrap();
+        });
+
+        event.dispatch();
+        event.dispatch();
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn it_pipes_values() {
+        let a = Observable::new(1);
+        let b = Observable::new(2);
+        let doubled = a.map(|value| value * 2);
+
+        let unsubscribe = doubled.pipe_to(&b);
+        assert_eq!(b.get(), 2);
+
+        a.set(3);
+        assert_eq!(b.get(), 6);
+
+        unsubscribe();
+        a.set(4);
+        assert_eq!(b.get(), 6);
+    }
+
+    #[test]
+    fn it_records_values() {
+        let observable = Observable::new(1);
+        let doubled = Derived::new(&[as_emitter(&observable)], {
+            let observable = observable.clone();
+            move || observable.get() * 2
+        });
+
+        let (values, unsubscribe) = observable.record();
+        let (doubled_values, _) = doubled.record();
+
+        observable.set(2);
+        unsubscribe();
+        observable.set(3);
+
+        assert_eq!(*values.lock().unwrap(), vec![1, 2]);
+        assert_eq!(*doubled_values.lock().unwrap(), vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn it_zips_two_readables() {
+        let a = Observable::new(1);
+        let b = Observable::new(String::from("x"));
+        let zipped = a.zip_with(&b, |a, b| b.repeat(*a));
+        assert_eq!(zipped.get(), "x");
+
+        a.set(3);
+        assert_eq!(zipped.get(), "xxx");
+
+        b.set(String::from("y"));
+        assert_eq!(zipped.get(), "yyy");
+    }
+
+    #[test]
+    fn it_stores_different_readables_together() {
+        let a = Observable::new(1);
+        let readables: Vec<Arc<dyn Readable<i32> + Send + Sync>> = vec![
+            a.clone().into_arc_dyn(),
+            a.map(|value| value * 2).into_arc_dyn(),
+            a.filter(|value| *value > 1).into_arc_dyn(),
+        ];
+        let sum = Arc::new(Mutex::new(0));
+
+        a.set(2);
+        assert_eq!(
+            readables.iter().map(|readable| readable.get()).sum::<i32>(),
+            8
+        );
+
+        let _: Vec<_> = readables
+            .iter()
+            .map(|readable| {
+                readable.subscribe_boxed(Box::new({
+                    let sum = sum.clone();
+                    move |value| *sum.lock().unwrap() += value
+                }))
+            })
+            .collect();
+        assert_eq!(*sum.lock().unwrap(), 8);
+    }
+
+    #[test]
+    fn it_uses_stores_as_trait_objects() {
+        let observable = Observable::new(1);
+        let event = Event::new();
+        let emitters: Vec<Box<dyn Emitter>> = vec![
+            Box::new(observable.clone()),
+            Box::new(event.clone()),
+            Box::new(observable.map(|value| value * 2)),
+        ];
+        let counter = Arc::new(Mutex::new(0));
+
+        let _: Vec<_> = emitters
+            .iter()
+            .map(|emitter| {
+                emitter.listen_boxed(Box::new({
+                    let counter = counter.clone();
+                    move || *counter.lock().unwrap() += 1
+                }))
+            })
+            .collect();
+
+        let writable: Arc<dyn Writable<i32>> = observable.clone();
+        writable.update_boxed(Box::new(|value| value + 1));
+        event.dispatch();
+        assert_eq!(observable.get(), 2);
+        assert_eq!(*counter.lock().unwrap(), 3);
+    }
+
+    #[test]
+    fn it_subscribes_filtered() {
+        let observable = Observable::new(1);
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = observable.subscribe_filtered(|value| value % 2 == 0, {
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+
+        for value in 2..=5 {
+            observable.set(value);
+        }
+        assert_eq!(*values.lock().unwrap(), vec![2, 4]);
+    }
+
+    #[test]
+    fn it_filters_the_immediate_call() {
+        let observable = Observable::new(2);
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = observable.subscribe_filtered(|value| value % 2 == 0, {
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+        assert_eq!(*values.lock().unwrap(), vec![2]);
+
+        let _ = observable.subscribe_filtered(|value| *value > 2, {
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+        assert_eq!(*values.lock().unwrap(), vec![2]);
+    }
+
+    #[test]
+    fn it_subscribes_distinct_by_key() {
+        #[derive(Clone)]
+        struct User {
+            id: u32,
+            visits: f64,
+        }
+
+        let observable = Observable::new(User { id: 1, visits: 0.0 });
+        let ids = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = observable.subscribe_distinct_by(|user| user.id, {
+            let ids = ids.clone();
+            move |user| ids.lock().unwrap().push(user.id)
+        });
+
+        observable.update(|user| User {
+            visits: user.visits + 1.0,
+            ..user.clone()
+        });
+        observable.set(User { id: 2, visits: 0.0 });
+        observable.set(User { id: 2, visits: 1.0 });
+        observable.set(User { id: 1, visits: 1.0 });
+
+        assert_eq!(*ids.lock().unwrap(), vec![1, 2, 1]);
+        assert_eq!(observable.get().visits, 1.0);
+    }
+
+    #[test]
+    fn it_dedups_per_subscription() {
+        let observable = Observable::new(1);
+        let plain = Arc::new(Mutex::new(Vec::new()));
+        let deduped = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = observable.subscribe({
+            let plain = plain.clone();
+            move |value| plain.lock().unwrap().push(*value)
+        });
+        observable.set(2);
+
+        let _ = observable.subscribe_deduped({
+            let deduped = deduped.clone();
+            move |value| deduped.lock().unwrap().push(*value)
+        });
+        observable.set(2);
+        observable.set(3);
+        observable.set(3);
+
+        assert_eq!(*plain.lock().unwrap(), vec![1, 2, 2, 3, 3]);
+        assert_eq!(*deduped.lock().unwrap(), vec![2, 3]);
+    }
+
+    #[test]
+    fn it_sets_from_another_readable() {
+        let source = Observable::new(1);
+        let destination = Observable::new(0);
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = destination.listen({
+            let counter = counter.clone();
+            move || *counter.lock().unwrap() += 1
+        });
+
+        destination.set_from(&*source);
+        assert_eq!(destination.get(), 1);
+
+        source.set(5);
+        destination.update_from(&*source, |current, source| current * 10 + source);
+        assert_eq!(destination.get(), 15);
+        assert_eq!(*counter.lock().unwrap(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_throttles_a_single_subscription() {
+        let observable = Observable::new(0);
+        let throttled = Arc::new(Mutex::new(Vec::new()));
+        let unthrottled = Arc::new(Mutex::new(0));
+
+        let _ = observable.subscribe_throttled(Duration::from_millis(100), {
+            let throttled = throttled.clone();
+            move |value| throttled.lock().unwrap().push(*value)
+        });
+        let _ = observable.listen({
+            let unthrottled = unthrottled.clone();
+            move || *unthrottled.lock().unwrap() += 1
+        });
+
+        for value in 1..=10 {
+            observable.set(value);
+        }
+        assert_eq!(*unthrottled.lock().unwrap(), 10);
+        assert_eq!(*throttled.lock().unwrap(), vec![0]);
+
+        thread::sleep(Duration::from_millis(300));
+        assert_eq!(*throttled.lock().unwrap(), vec![0, 10]);
+    }
 }
@@ -0,0 +1,141 @@
+use core::convert::Infallible;
+
+/// The state of a value that is loaded asynchronously or may fail to load.
+///
+/// Meant to be stored in an observable, e.g. `Observable<Loadable<User, Error>>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Loadable<Value, Error = Infallible> {
+    /// The value is being loaded.
+    Loading,
+    /// The value has been loaded.
+    Ready(Value),
+    /// Loading the value failed.
+    Failed(Error),
+}
+
+impl<Value, Error> Loadable<Value, Error> {
+    /// Returns whether the value is being loaded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Loadable;
+    /// assert!(Loadable::<i32>::Loading.is_loading());
+    /// ```
+    pub fn is_loading(&self) -> bool {
+        matches!(self, Self::Loading)
+    }
+
+    /// Returns the loaded value, if it is ready.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Loadable;
+    /// assert_eq!(Loadable::<i32>::Ready(1).ready(), Some(&1));
+    /// ```
+    pub fn ready(&self) -> Option<&Value> {
+        match self {
+            Self::Ready(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the error, if loading failed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Loadable;
+    /// assert_eq!(Loadable::<i32, &str>::Failed("failed").failed(), Some(&"failed"));
+    /// ```
+    pub fn failed(&self) -> Option<&Error> {
+        match self {
+            Self::Failed(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Maps the loaded value, keeping the loading and failed states.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Loadable;
+    /// let loadable = Loadable::<i32>::Ready(1);
+    /// assert_eq!(loadable.map_ready(|value| value * 2), Loadable::Ready(2));
+    /// ```
+    pub fn map_ready<Out>(self, map: impl FnOnce(Value) -> Out) -> Loadable<Out, Error> {
+        match self {
+            Self::Loading => Loadable::Loading,
+            Self::Ready(value) => Loadable::Ready(map(value)),
+            Self::Failed(error) => Loadable::Failed(error),
+        }
+    }
+
+    /// Combines two loadables, which is only ready once both are.
+    ///
+    /// The first failure takes precedence, otherwise it is loading while either one is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Loadable;
+    /// let both = Loadable::<i32>::Ready(1).zip(Loadable::Ready("a"));
+    /// assert_eq!(both, Loadable::Ready((1, "a")));
+    /// ```
+    pub fn zip<Other>(self, other: Loadable<Other, Error>) -> Loadable<(Value, Other), Error> {
+        match (self, other) {
+            (Self::Failed(error), _) | (_, Loadable::Failed(error)) => Loadable::Failed(error),
+            (Self::Ready(value), Loadable::Ready(other)) => Loadable::Ready((value, other)),
+            _ => Loadable::Loading,
+        }
+    }
+}
+
+impl<Value, Error> From<Result<Value, Error>> for Loadable<Value, Error> {
+    fn from(result: Result<Value, Error>) -> Self {
+        match result {
+            Ok(value) => Self::Ready(value),
+            Err(error) => Self::Failed(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_accesses_states() {
+        let loading = Loadable::<i32, &str>::Loading;
+        assert!(loading.is_loading());
+        assert_eq!(loading.ready(), None);
+        assert_eq!(loading.failed(), None);
+
+        let ready = Loadable::<i32, &str>::from(Ok(1));
+        assert!(!ready.is_loading());
+        assert_eq!(ready.ready(), Some(&1));
+
+        let failed = Loadable::<i32, &str>::from(Err("failed"));
+        assert_eq!(failed.failed(), Some(&"failed"));
+        assert_eq!(
+            failed.map_ready(|value| value * 2),
+            Loadable::Failed("failed")
+        );
+    }
+
+    #[test]
+    fn it_short_circuits_when_zipping() {
+        let ready = Loadable::<i32, &str>::Ready(1);
+        assert_eq!(
+            ready.clone().zip(Loadable::<i32, _>::Loading),
+            Loadable::Loading
+        );
+        assert_eq!(
+            Loadable::<i32, _>::Loading.zip(Loadable::<i32, _>::Failed("failed")),
+            Loadable::Failed("failed")
+        );
+        assert_eq!(ready.clone().zip(ready), Loadable::Ready((1, 1)));
+    }
+}
@@ -0,0 +1,129 @@
+#[cfg(not(any(feature = "std", feature = "spin")))]
+compile_error!("Either the `std` or the `spin` feature has to be enabled.");
+
+#[cfg(not(any(feature = "spin", feature = "parking_lot")))]
+use std::sync as backend;
+
+#[cfg(feature = "parking_lot")]
+use parking_lot as backend;
+
+// `parking_lot` takes precedence, so enabling both backends keeps the features additive.
+#[cfg(all(feature = "spin", not(feature = "parking_lot")))]
+use spin as backend;
+
+pub(crate) use backend::{MutexGuard, RwLockReadGuard, RwLockWriteGuard};
+
+/// Reader-writer lock used by all stores.
+///
+/// Wraps `std::sync::RwLock` by default, `spin::RwLock` if the `spin` feature is enabled
+/// and `parking_lot::RwLock` if the `parking_lot` feature is enabled, even along with `spin`.
+pub(crate) struct RwLock<T>(backend::RwLock<T>);
+
+impl<T> RwLock<T> {
+    /// Creates a new lock holding the given value.
+    pub(crate) const fn new(value: T) -> Self {
+        Self(backend::RwLock::new(value))
+    }
+
+    /// Locks for shared read access.
+    pub(crate) fn read(&self) -> RwLockReadGuard<'_, T> {
+        #[cfg(not(any(feature = "spin", feature = "parking_lot")))]
+        return self.0.read().unwrap();
+
+        #[cfg(any(feature = "spin", feature = "parking_lot"))]
+        return self.0.read();
+    }
+
+    /// Locks for exclusive write access.
+    pub(crate) fn write(&self) -> RwLockWriteGuard<'_, T> {
+        #[cfg(not(any(feature = "spin", feature = "parking_lot")))]
+        return self.0.write().unwrap();
+
+        #[cfg(any(feature = "spin", feature = "parking_lot"))]
+        return self.0.write();
+    }
+
+    /// Consumes the lock and returns the value.
+    pub(crate) fn into_inner(self) -> T {
+        #[cfg(not(any(feature = "spin", feature = "parking_lot")))]
+        return self.0.into_inner().unwrap();
+
+        #[cfg(any(feature = "spin", feature = "parking_lot"))]
+        return self.0.into_inner();
+    }
+}
+
+/// Mutual exclusion lock used for callbacks that need mutable access.
+///
+/// Wraps the `Mutex` of the same backend as `RwLock`.
+pub(crate) struct Mutex<T>(backend::Mutex<T>);
+
+impl<T> Mutex<T> {
+    /// Creates a new lock holding the given value.
+    pub(crate) const fn new(value: T) -> Self {
+        Self(backend::Mutex::new(value))
+    }
+
+    /// Locks for exclusive access.
+    pub(crate) fn lock(&self) -> MutexGuard<'_, T> {
+        #[cfg(not(any(feature = "spin", feature = "parking_lot")))]
+        return self.0.lock().unwrap();
+
+        #[cfg(any(feature = "spin", feature = "parking_lot"))]
+        return self.0.lock();
+    }
+
+    /// Locks for exclusive access, even if a panic while holding the lock poisoned it.
+    pub(crate) fn lock_ignoring_poison(&self) -> MutexGuard<'_, T> {
+        #[cfg(not(any(feature = "spin", feature = "parking_lot")))]
+        return self
+            .0
+            .lock()
+            .unwrap_or_else(backend::PoisonError::into_inner);
+
+        #[cfg(any(feature = "spin", feature = "parking_lot"))]
+        return self.0.lock();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use super::*;
+
+    #[test]
+    fn it_reads_and_writes() {
+        let lock = RwLock::new(1);
+        assert_eq!(*lock.read(), 1);
+
+        *lock.write() = 2;
+        assert_eq!(*lock.read(), 2);
+        assert_eq!(lock.into_inner(), 2);
+    }
+
+    #[test]
+    fn it_allows_concurrent_readers() {
+        let lock = RwLock::new(1);
+        let first = lock.read();
+        let second = lock.read();
+
+        assert_eq!(*first + *second, 2);
+    }
+
+    #[test]
+    fn it_works_in_threads() {
+        let lock = Arc::new(RwLock::new(0));
+
+        (0..10)
+            .map(|_| {
+                let lock = lock.clone();
+                thread::spawn(move || {
+                    *lock.write() += 1;
+                })
+            })
+            .for_each(|thread| thread.join().unwrap());
+
+        assert_eq!(*lock.read(), 10);
+    }
+}
@@ -0,0 +1,323 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::fmt::Debug;
+
+use crate::{Emitter, ReadRef, Readable, Subscribers, Unsubscribe, lock::RwLock};
+
+/// A readable observable value that maps the value of another readable.
+pub struct Mapped<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    value: RwLock<Value>,
+    subscribers: Subscribers<Value>,
+}
+
+impl<Value> Mapped<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    /// Creates a new mapped value.
+    ///
+    /// The mapping function is run whenever the source value changes.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Mapped, Readable};
+    /// let observable = Observable::new(1);
+    /// let doubled = Mapped::new(&observable, |value| value * 2);
+    /// assert_eq!(doubled.get(), 2);
+    /// ```
+    pub fn new<Source>(
+        source: &Arc<impl Readable<Source> + Send + Sync + 'static>,
+        map: impl Fn(&Source) -> Value + Send + Sync + 'static,
+    ) -> Arc<Self>
+    where
+        Source: Clone + Send + Sync,
+    {
+        let instance = Arc::new(Self {
+            value: RwLock::new(map(&source.get())),
+            subscribers: Subscribers::new(),
+        });
+
+        let unsubscribe = source.subscribe({
+            let instance = Arc::downgrade(&instance);
+            move |value| {
+                if let Some(instance) = instance.upgrade() {
+                    *instance.value.write() = map(value);
+                    instance.notify();
+                }
+            }
+        });
+        instance.subscribers.hold(unsubscribe);
+        instance.subscribers.keep(source.clone());
+
+        instance
+    }
+
+    /// Creates a new mapped value that reads the source by reference.
+    ///
+    /// Unlike `new`, the source value is not cloned to compute the initial value.
+    /// The mapping function is run whenever the source value changes.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Mapped, ReadRef};
+    /// let observable = Observable::new(vec![1, 2, 3]);
+    /// let len = Mapped::from_ref(&observable, |value| value.len());
+    /// assert_eq!(len.with(|len| *len), 3);
+    /// ```
+    pub fn from_ref<Source>(
+        source: &Arc<impl Readable<Source> + ReadRef<Source> + Emitter + Send + Sync + 'static>,
+        map: impl Fn(&Source) -> Value + Send + Sync + 'static,
+    ) -> Arc<Self>
+    where
+        Source: Clone + Send + Sync,
+    {
+        let instance = Arc::new(Self {
+            value: RwLock::new(source.with(&map)),
+            subscribers: Subscribers::new(),
+        });
+
+        // The source keeps this callback alive, so it may only reference the source weakly.
+        let unsubscribe = source.listen_boxed(Box::new({
+            let instance = Arc::downgrade(&instance);
+            let source = Arc::downgrade(source);
+            move || {
+                if let Some(instance) = instance.upgrade()
+                    && let Some(source) = source.upgrade()
+                {
+                    *instance.value.write() = source.with(&map);
+                    instance.notify();
+                }
+            }
+        }));
+        instance.subscribers.hold(unsubscribe);
+        instance.subscribers.keep(source.clone());
+
+        instance
+    }
+
+    /// Names the store, so its changes are reported to the global hook.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Mapped};
+    /// let mapped = Mapped::new(&Observable::new(1), |value| value * 2);
+    /// mapped.name("mapped");
+    /// ```
+    #[cfg(feature = "debug")]
+    pub fn name(&self, name: &str)
+    where
+        Value: Debug,
+    {
+        self.subscribers.name(name);
+    }
+
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Mapped};
+    /// let observable = Observable::new(1);
+    /// let doubled = Mapped::new(&observable, |value| value * 2);
+    /// assert_eq!(doubled.subscriber_count(), 0);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count()
+    }
+
+    /// Subscribes to value changes on behalf of a weakly referenced owner.
+    ///
+    /// Only a weak reference to `owner` is kept, so the subscription does not keep it alive.
+    /// The callback is run immediately and on every change as long as the owner exists.
+    /// Once the owner is dropped, the subscription removes itself on the next change.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use stores::{Mapped, Observable};
+    /// let store = Mapped::new(&Observable::new(1), |value| value * 2);
+    /// let owner = Arc::new(Mutex::new(0));
+    /// let unsubscribe = store.subscribe_weak(&owner, |owner, value| {
+    ///     *owner.lock().unwrap() = *value;
+    /// });
+    /// ```
+    pub fn subscribe_weak<Owner>(
+        &self,
+        owner: &Arc<Owner>,
+        callback: impl Fn(&Owner, &Value) + Send + Sync + 'static,
+    ) -> Unsubscribe
+    where
+        Owner: Send + Sync + 'static,
+    {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe_weak(value, owner, callback)
+    }
+
+    /// Internal function to run all registered callbacks.
+    fn notify(&self) {
+        let value = self.value.read().clone();
+        self.subscribers.notify(&value);
+    }
+}
+
+impl<Value> Emitter for Mapped<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.subscribers.listen(callback)
+    }
+}
+
+impl<Value> Readable<Value> for Mapped<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn get(&self) -> Value {
+        self.value.read().clone()
+    }
+
+    fn subscribe_boxed(&self, callback: Box<dyn Fn(&Value) + Send + Sync>) -> Unsubscribe {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe(value, callback)
+    }
+}
+
+impl<Value> ReadRef<Value> for Mapped<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    fn with<R>(&self, reader: impl FnOnce(&Value) -> R) -> R {
+        reader(&self.value.read())
+    }
+}
+
+impl<Value> Debug for Mapped<Value>
+where
+    Value: Debug + Clone + Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Mapped")
+            .field("value", &self.value.read())
+            .field("callbacks", &self.subscribers)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{Observable, Writable};
+
+    use super::*;
+
+    #[test]
+    fn it_maps() {
+        let observable = Observable::new(1);
+        let mapped = Mapped::new(&observable, |value| value.to_string());
+
+        assert_eq!(mapped.get(), "1");
+
+        observable.set(2);
+        assert_eq!(mapped.get(), "2");
+    }
+
+    #[test]
+    fn it_triggers_emitter_on_change() {
+        let observable = Observable::new(1);
+        let mapped = Mapped::new(&observable, |value| value * 2);
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = mapped.listen({
+            let counter = counter.clone();
+            move || {
+                *counter.lock().unwrap() += 1;
+            }
+        });
+
+        assert_eq!(counter.lock().unwrap().clone(), 0);
+
+        observable.set(2);
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+    }
+
+    #[test]
+    fn it_unsubscribes_from_source_on_drop() {
+        let observable = Observable::new(vec![1, 2, 3]);
+        let mapped = Mapped::new(&observable, |value| value.len());
+        let from_ref = Mapped::from_ref(&observable, |value| value.len());
+        assert_eq!(observable.subscriber_count(), 2);
+
+        drop(mapped);
+        drop(from_ref);
+        assert_eq!(observable.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_provides_value_to_subscription() {
+        let observable = Observable::new(1);
+        let mapped = Mapped::new(&observable, |value| value * 2);
+        let counter = Arc::new(Mutex::new(0));
+
+        let unsubscribe = mapped.subscribe({
+            let counter = counter.clone();
+            move |value| {
+                *counter.lock().unwrap() = *value;
+            }
+        });
+
+        assert_eq!(counter.lock().unwrap().clone(), 2);
+
+        observable.set(2);
+        assert_eq!(counter.lock().unwrap().clone(), 4);
+
+        unsubscribe();
+        observable.set(3);
+        assert_eq!(counter.lock().unwrap().clone(), 4);
+    }
+
+    struct Parent {
+        clones: Arc<Mutex<usize>>,
+        name: String,
+    }
+
+    impl Clone for Parent {
+        fn clone(&self) -> Self {
+            *self.clones.lock().unwrap() += 1;
+            Self {
+                clones: self.clones.clone(),
+                name: self.name.clone(),
+            }
+        }
+    }
+
+    #[test]
+    fn it_maps_by_reference() {
+        let clones = Arc::new(Mutex::new(0));
+        let observable = Observable::new(Parent {
+            clones: clones.clone(),
+            name: String::from("a"),
+        });
+        // Creating the observable keeps a copy of the initial value for `reset`.
+        *clones.lock().unwrap() = 0;
+        let name = observable.map_ref(|parent| parent.name.clone());
+        assert_eq!(*clones.lock().unwrap(), 0);
+
+        let len = name.with(|name| name.len());
+        assert_eq!(len, 1);
+        assert_eq!(*clones.lock().unwrap(), 0);
+
+        observable.modify(|parent| parent.name = String::from("ab"));
+        assert!(name.with(|name| name == "ab"));
+    }
+}
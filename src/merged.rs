@@ -0,0 +1,120 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::fmt::Debug;
+
+use crate::{Emitter, Event, Unsubscribe};
+
+/// An emitter that fires whenever any of its targets fires.
+///
+/// A lightweight alternative to `Derived` when no value is needed.
+/// Unsubscribes from all targets once it is dropped.
+pub struct Merged {
+    event: Arc<Event>,
+    sources: Vec<Unsubscribe>,
+}
+
+impl Merged {
+    /// Creates a new emitter that merges all targets.
+    ///
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Emitter, Merged, Observable, Writable};
+    /// let a = Observable::new(1);
+    /// let b = Observable::new("b");
+    /// let merged = Merged::new(&[a.clone(), b.clone()]);
+    ///
+    /// let unsubscribe = merged.listen(|| println!("Something changed"));
+    /// a.set(2); // "Something changed"
+    /// b.set("c"); // "Something changed"
+    /// ```
+    pub fn new(targets: &[Arc<dyn Emitter + Send + Sync>]) -> Arc<Self> {
+        let event = Event::new();
+        let sources = targets
+            .iter()
+            .map(|target| {
+                target.listen_boxed(Box::new({
+                    let event = event.clone();
+                    move || event.dispatch()
+                }))
+            })
+            .collect();
+
+        Arc::new(Self { event, sources })
+    }
+
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Merged, Observable};
+    /// let merged = Merged::new(&[Observable::new(1)]);
+    /// assert_eq!(merged.subscriber_count(), 0);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.event.subscriber_count()
+    }
+}
+
+impl Emitter for Merged {
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.event.listen_boxed(callback)
+    }
+}
+
+impl Drop for Merged {
+    fn drop(&mut self) {
+        self.sources.iter().for_each(|unsubscribe| unsubscribe());
+    }
+}
+
+impl Debug for Merged {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Merged")
+            .field("sources", &self.sources.len())
+            .field("callbacks", &self.event.subscriber_count())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{Observable, Writable};
+
+    use super::*;
+
+    #[test]
+    fn it_fires_when_any_target_fires() {
+        let a = Observable::new(1);
+        let b = Observable::new(String::from("b"));
+        let merged = Merged::new(&[a.clone(), b.clone()]);
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = merged.listen({
+            let counter = counter.clone();
+            move || *counter.lock().unwrap() += 1
+        });
+
+        a.set(2);
+        b.set(String::from("c"));
+        a.set(3);
+        assert_eq!(*counter.lock().unwrap(), 3);
+    }
+
+    #[test]
+    fn it_unsubscribes_from_targets_on_drop() {
+        let a = Observable::new(1);
+        let b = Observable::new(2);
+        let merged = Merged::new(&[a.clone(), b.clone()]);
+        assert_eq!(a.subscriber_count(), 1);
+        assert_eq!(b.subscriber_count(), 1);
+
+        drop(merged);
+        assert_eq!(a.subscriber_count(), 0);
+        assert_eq!(b.subscriber_count(), 0);
+    }
+}
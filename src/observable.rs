@@ -1,24 +1,57 @@
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use core::{
+    any::Any,
+    fmt::Debug,
+    mem,
+    ops::{Add, ControlFlow, Deref, DerefMut, Mul, Sub},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+#[cfg(feature = "std")]
 use std::{
     collections::HashMap,
-    fmt::Debug,
-    sync::{Arc, RwLock},
+    hash::Hash,
+    sync::{
+        Condvar, Mutex,
+        mpsc::{self, Receiver, Sender},
+    },
+    thread,
+    time::Duration,
 };
 
-use crate::{Callback, Emitter, Readable, Writable};
+#[cfg(feature = "tokio")]
+use tokio::sync::broadcast;
+
+#[cfg(feature = "tokio")]
+use crate::AsyncDerived;
+
+use crate::{
+    Callback, Derived, Emitter, Loadable, Mapped, ReadRef, Readable, Subscribers, Unsubscribe,
+    WeakObservable, Writable, as_emitter,
+    lock::{self, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+#[cfg(feature = "std")]
+use crate::{depth, transaction};
 
 /// A readable and writable observable value.
+///
+/// Callbacks may change the observable they are registered on. With the `std` feature, such a
+/// change is not notified immediately, but once all callbacks have been run for the current value.
+/// The callbacks that made the change are not notified about it again.
 pub struct Observable<Value>
 where
-    Value: Clone + Send + Sync,
+    Value: Send + Sync,
 {
     value: RwLock<Value>,
-    callbacks: RwLock<HashMap<usize, Callback<Value>>>,
-    counter: RwLock<usize>,
+    version: AtomicU64,
+    initial: Option<Value>,
+    subscribers: Subscribers<Value>,
+    /// Keys of keyed subscriptions with the id of their callback.
+    keys: RwLock<Vec<(Box<dyn Any + Send + Sync>, usize)>>,
 }
 
 impl<Value> Observable<Value>
 where
-    Value: Clone + Send + Sync,
+    Value: Send + Sync,
 {
     /// Creates a new observable value.
     ///
@@ -30,244 +63,2945 @@ where
     /// use stores::Observable;
     /// let observable = Observable::new(1);
     /// ```
-    pub fn new(value: Value) -> Arc<Self> {
+    pub fn new(value: Value) -> Arc<Self>
+    where
+        Value: Clone,
+    {
+        Self::from_parts(value.clone(), Some(value))
+    }
+
+    /// Creates a new observable for a value that cannot be cloned.
+    ///
+    /// Unlike `new`, the initial value is not kept, so `reset` keeps the current value.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Mutex;
+    /// use stores::{Observable, ReadRef};
+    /// let observable = Observable::new_uncloned(Mutex::new(1));
+    /// assert_eq!(observable.with(|value| *value.lock().unwrap()), 1);
+    /// ```
+    pub fn new_uncloned(value: Value) -> Arc<Self> {
+        Self::from_parts(value, None)
+    }
+
+    /// Internal function to create a new observable that restores `initial` on reset.
+    fn from_parts(value: Value, initial: Option<Value>) -> Arc<Self> {
         Arc::new(Self {
             value: RwLock::new(value),
-            callbacks: RwLock::new(HashMap::new()),
-            counter: RwLock::new(0),
+            version: AtomicU64::new(0),
+            initial,
+            subscribers: Subscribers::new(),
+            keys: RwLock::new(Vec::new()),
         })
     }
 
-    /// Internal function to run all registered callbacks.
-    fn notify(&self) {
-        let value = self.value.read().unwrap().clone();
-        for callback in self.callbacks.read().unwrap().values() {
-            match callback {
-                Callback::Subscriber(func) => func(&value),
-                Callback::Listener(func) => func(),
-            }
-        }
+    /// Creates a new observable whose initial value is produced by a closure.
+    ///
+    /// The initializer is run exactly once.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let observable = Observable::new_with(|| (1..=3).sum::<i32>());
+    /// assert_eq!(observable.get(), 6);
+    /// ```
+    pub fn new_with(init: impl FnOnce() -> Value) -> Arc<Self>
+    where
+        Value: Clone,
+    {
+        Self::new(init())
+    }
+
+    /// Creates a new observable holding the default value.
+    ///
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let observable = Observable::<i32>::default();
+    /// assert_eq!(observable.get(), 0);
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn default() -> Arc<Self>
+    where
+        Value: Default + Clone,
+    {
+        Self::new(Value::default())
+    }
+
+    /// Names the store, so its changes are reported to the global hook.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Observable;
+    /// let observable = Observable::new(1);
+    /// observable.name("observable");
+    /// ```
+    #[cfg(feature = "debug")]
+    pub fn name(&self, name: &str)
+    where
+        Value: Debug,
+    {
+        self.subscribers.name(name);
+    }
+
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable};
+    /// let observable = Observable::new(1);
+    /// assert_eq!(observable.subscriber_count(), 0);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count()
+    }
+
+    /// Returns the version of the value, which increases with every change.
+    ///
+    /// Allows to check whether the value changed since it was last looked at, without
+    /// subscribing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Writable};
+    /// let observable = Observable::new(1);
+    /// let version = observable.version();
+    ///
+    /// observable.set(2);
+    /// assert!(observable.version() > version);
+    /// ```
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+
+    /// Borrows the current value without cloning it.
+    ///
+    /// The value stays read locked for as long as the returned borrow is held.
+    /// Other readers are not affected, but every writer blocks until the borrow is dropped,
+    /// so it must not be held while writing to the same store.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Observable;
+    /// let observable = Observable::new(vec![1, 2, 3]);
+    /// assert_eq!(observable.peek_ref().len(), 3);
+    /// ```
+    pub fn peek_ref(&self) -> impl Deref<Target = Value> + '_ {
+        self.read()
+    }
+
+    /// Returns the value, if the given handle is the only one to the observable.
+    ///
+    /// Otherwise the handle is returned unchanged.
+    /// Allows reclaiming large values at teardown without cloning them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Observable;
+    /// let observable = Observable::new(vec![1, 2, 3]);
+    /// let other = observable.clone();
+    ///
+    /// let observable = Observable::try_unwrap(observable).unwrap_err();
+    /// drop(other);
+    /// assert_eq!(Observable::try_unwrap(observable).ok(), Some(vec![1, 2, 3]));
+    /// ```
+    pub fn try_unwrap(store: Arc<Self>) -> Result<Value, Arc<Self>> {
+        Arc::try_unwrap(store).map(|store| store.value.into_inner())
+    }
+
+    /// Creates a weak handle that does not keep the observable alive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Observable;
+    /// let observable = Observable::new(1);
+    /// let weak = observable.downgrade();
+    /// assert!(weak.upgrade().is_some());
+    /// ```
+    pub fn downgrade(self: &Arc<Self>) -> WeakObservable<Value> {
+        WeakObservable::new(self)
+    }
+
+    /// Returns a guard to read the current value by reference.
+    ///
+    /// An escape hatch for values that are expensive to clone, where `with` is too unwieldy.
+    /// The value stays read locked for as long as the guard is held.
+    /// Every writer blocks until the guard is dropped, so it must not be held while writing to
+    /// the same store, which would deadlock.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Observable;
+    /// let observable = Observable::new((String::from("name"), vec![1, 2, 3]));
+    /// let value = observable.read();
+    /// assert_eq!(value.0, "name");
+    /// assert_eq!(value.1.len(), 3);
+    /// ```
+    pub fn read(&self) -> ReadGuard<'_, Value> {
+        ReadGuard {
+            guard: self.value.read(),
+        }
+    }
+}
+
+impl<Value> Observable<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    /// Reads the current value without any side effects.
+    ///
+    /// Behaves like `get`, but signals that the read is only used for inspection, e.g. logging.
+    /// The value is only locked while it is cloned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Observable;
+    /// let observable = Observable::new(1);
+    /// assert_eq!(observable.peek(), 1);
+    /// ```
+    pub fn peek(&self) -> Value {
+        self.value.read().clone()
+    }
+
+    /// Creates an independent copy holding the current value.
+    ///
+    /// The fork has no callbacks and does not stay in sync: changes to either observable are
+    /// not reflected in the other one. Resetting the fork restores the value it was forked with.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable, Writable};
+    /// let observable = Observable::new(1);
+    /// let fork = observable.fork();
+    ///
+    /// fork.set(2);
+    /// assert_eq!(observable.get(), 1);
+    /// ```
+    pub fn fork(&self) -> Arc<Self> {
+        Self::new(self.value.read().clone())
+    }
+
+    /// Modifies the internal value in place.
+    ///
+    /// Unlike `update`, this does not require cloning the current value.
+    /// Calling this will trigger all registered callbacks once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let observable = Observable::new(vec![1, 2]);
+    /// observable.modify(|value| value.push(3));
+    /// assert_eq!(observable.get(), vec![1, 2, 3]);
+    /// ```
+    pub fn modify(&self, modifier: impl FnOnce(&mut Value)) {
+        {
+            let mut value = self.value.write();
+            modifier(&mut value);
+            self.version.fetch_add(1, Ordering::AcqRel);
+        }
+        self.notify();
+    }
+
+    /// Returns a guard to modify the internal value in place.
+    ///
+    /// The value stays write locked for as long as the guard is held. Once it is dropped,
+    /// the lock is released first and all registered callbacks are triggered once afterwards.
+    /// Reading or writing the same store while holding the guard deadlocks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let observable = Observable::new((String::from("name"), 1));
+    /// {
+    ///     let mut value = observable.modify_guard();
+    ///     value.1 = 5;
+    /// }
+    /// assert_eq!(observable.get().1, 5);
+    /// ```
+    pub fn modify_guard(&self) -> ModifyGuard<'_, Value> {
+        ModifyGuard {
+            observable: self,
+            guard: Some(self.value.write()),
+        }
+    }
+
+    /// Replaces the internal value with one computed from it and returns the previous value.
+    ///
+    /// Reading, computing and writing happen under a single lock,
+    /// so no concurrent write can slip in between.
+    /// Calling this will trigger all registered callbacks once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let observable = Observable::new(1);
+    /// assert_eq!(observable.replace_with(|value| value + 1), 1);
+    /// assert_eq!(observable.get(), 2);
+    /// ```
+    pub fn replace_with(&self, replacer: impl FnOnce(&Value) -> Value) -> Value {
+        let previous = {
+            let mut value = self.value.write();
+            let next = replacer(&value);
+            self.version.fetch_add(1, Ordering::AcqRel);
+            mem::replace(&mut *value, next)
+        };
+        self.notify();
+        previous
+    }
+
+    /// Restores the value the observable was created with.
+    ///
+    /// Calling this will trigger all registered callbacks once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable, Writable};
+    /// let observable = Observable::new(1);
+    /// observable.set(2);
+    ///
+    /// observable.reset();
+    /// assert_eq!(observable.get(), 1);
+    /// ```
+    pub fn reset(&self) {
+        self.set(self.initial.clone().unwrap_or_else(|| self.peek()));
+    }
+
+    /// Sets a new value without notifying any callbacks.
+    ///
+    /// Useful for hydrating state, e.g. from storage, before the callbacks should react to it.
+    /// The callbacks can be notified afterwards via `notify_now`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let observable = Observable::new(1);
+    /// observable.set_silent(2);
+    /// assert_eq!(observable.get(), 2);
+    /// ```
+    pub fn set_silent(&self, value: Value) {
+        let mut current = self.value.write();
+        *current = value;
+        self.version.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Runs all registered callbacks with the current value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Observable;
+    /// let observable = Observable::new(1);
+    /// observable.set_silent(2);
+    /// observable.notify_now();
+    /// ```
+    pub fn notify_now(&self) {
+        self.notify();
+    }
+
+    /// Subscribes to value changes, choosing whether the callback is run immediately.
+    ///
+    /// With `immediate` set to `true` this behaves like `subscribe`,
+    /// otherwise the callback is only run for values emitted afterwards.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Observable;
+    /// let observable = Observable::new(1);
+    /// let unsubscribe = observable.subscribe_with_opts(false, |value| println!("{}", value));
+    /// ```
+    pub fn subscribe_with_opts(
+        &self,
+        immediate: bool,
+        callback: impl Fn(&Value) + Send + Sync + 'static,
+    ) -> Unsubscribe {
+        if immediate {
+            let value = self.value.read().clone();
+            callback(&value);
+        }
+
+        let callback = Box::new(callback);
+        self.subscribers.register(Callback::Subscriber(callback))
+    }
+
+    /// Subscribes to value changes and returns the current value alongside the handle.
+    ///
+    /// Behaves like `subscribe`, but also returns the value passed to the immediate call,
+    /// which saves a separate `get`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Observable;
+    /// let observable = Observable::new(1);
+    /// let (value, unsubscribe) = observable.subscribe_get(|value| println!("{}", value));
+    /// assert_eq!(value, 1);
+    /// ```
+    pub fn subscribe_get(
+        &self,
+        callback: impl Fn(&Value) + Send + Sync + 'static,
+    ) -> (Value, Unsubscribe) {
+        let value = self.value.read().clone();
+        callback(&value);
+
+        (value, self.subscribe_with_opts(false, callback))
+    }
+
+    /// Subscribes to value changes on behalf of a weakly referenced owner.
+    ///
+    /// Only a weak reference to `owner` is kept, so the subscription does not keep it alive.
+    /// The callback is run immediately and on every change as long as the owner exists.
+    /// Once the owner is dropped, the subscription removes itself on the next change.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use stores::Observable;
+    /// let store = Observable::new(1);
+    /// let owner = Arc::new(Mutex::new(0));
+    /// let unsubscribe = store.subscribe_weak(&owner, |owner, value| {
+    ///     *owner.lock().unwrap() = *value;
+    /// });
+    /// ```
+    pub fn subscribe_weak<Owner>(
+        &self,
+        owner: &Arc<Owner>,
+        callback: impl Fn(&Owner, &Value) + Send + Sync + 'static,
+    ) -> Unsubscribe
+    where
+        Owner: Send + Sync + 'static,
+    {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe_weak(value, owner, callback)
+    }
+
+    /// Subscribes to value changes until the callback breaks.
+    ///
+    /// The callback is run immediately and on every change until it returns
+    /// `ControlFlow::Break`, afterwards it is removed and never run again.
+    /// It returns a handle that can be called to unsubscribe earlier.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::ops::ControlFlow;
+    /// use stores::Observable;
+    /// let observable = Observable::new(1);
+    /// let unsubscribe = observable.subscribe_until(|value| {
+    ///     if *value > 10 {
+    ///         return ControlFlow::Break(());
+    ///     }
+    ///     ControlFlow::Continue(())
+    /// });
+    /// ```
+    pub fn subscribe_until(
+        &self,
+        callback: impl Fn(&Value) -> ControlFlow<()> + Send + Sync + 'static,
+    ) -> Unsubscribe {
+        let value = self.value.read().clone();
+        if callback(&value).is_break() {
+            return Unsubscribe::new(|| {});
+        }
+
+        // Concurrent notifications may still reach the callback before it is removed.
+        let done = AtomicBool::new(false);
+        let callback = Box::new(move |value: &Value| {
+            if done.load(Ordering::Acquire) {
+                return false;
+            }
+
+            let proceed = callback(value).is_continue();
+            if !proceed {
+                done.store(true, Ordering::Release);
+            }
+            proceed
+        });
+        self.subscribers.register(Callback::Weak(callback))
+    }
+
+    /// Subscribes to value changes with a priority.
+    ///
+    /// The callback is run immediately and whenever the value changes. On every change,
+    /// callbacks run in ascending priority, those with equal priority in order of registration.
+    /// All other subscriptions and listeners have a priority of `0`.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let observable = Observable::new(1);
+    /// let _ = observable.subscribe(|value| println!("render {}", value));
+    /// let _ = observable.subscribe_priority(-1, |value| println!("cache {}", value));
+    /// ```
+    pub fn subscribe_priority(
+        &self,
+        priority: i32,
+        callback: impl Fn(&Value) + Send + Sync + 'static,
+    ) -> Unsubscribe {
+        let value = self.value.read().clone();
+        callback(&value);
+
+        self.subscribers.insert(
+            (priority, self.subscribers.next_id()),
+            Callback::Subscriber(Box::new(callback)),
+        )
+    }
+
+    /// Subscribes to value changes under a key, replacing any callback registered with it.
+    ///
+    /// The callback is run immediately and whenever the value changes. Registering another
+    /// callback with an equal key replaces this one in place, so a callback that is subscribed
+    /// repeatedly, e.g. on every render, still only runs once per change.
+    /// It returns a handle that can be called to unsubscribe, which removes the callback
+    /// currently registered with the key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Observable;
+    /// let observable = Observable::new(1);
+    /// let _ = observable.subscribe_keyed("render", |value| println!("{}", value));
+    /// let _ = observable.subscribe_keyed("render", |value| println!("{}", value));
+    /// assert_eq!(observable.subscriber_count(), 1);
+    /// ```
+    pub fn subscribe_keyed<Key>(
+        &self,
+        key: Key,
+        callback: impl Fn(&Value) + Send + Sync + 'static,
+    ) -> Unsubscribe
+    where
+        Key: PartialEq + Send + Sync + 'static,
+    {
+        let value = self.value.read().clone();
+        callback(&value);
+
+        let mut keys = self.keys.write();
+        // Keys of callbacks that have been unsubscribed in the meantime are dropped.
+        keys.retain(|(_, id)| self.subscribers.callbacks().read().contains_key(&(0, *id)));
+
+        let existing = keys
+            .iter()
+            .find(|(existing, _)| existing.downcast_ref::<Key>() == Some(&key))
+            .map(|(_, id)| *id);
+        let id = existing.unwrap_or_else(|| {
+            let id = self.subscribers.next_id();
+            keys.push((Box::new(key), id));
+            id
+        });
+
+        self.subscribers
+            .insert((0, id), Callback::Subscriber(Box::new(callback)))
+    }
+
+    /// Subscribes to value changes for the duration of `scope`.
+    ///
+    /// Unlike `subscribe`, the callback does not have to be `'static`, so it can borrow from the
+    /// enclosing scope. The callback is run immediately and on every change until `scope` returns
+    /// or panics, afterwards it is removed. A closure is used instead of a guard, since a guard
+    /// could be leaked, which would leave a dangling callback behind. If a notification on another
+    /// thread is still running the callback when `scope` returns, this waits for it to finish.
+    /// Without the `std` feature, the callback must not change the observable itself.
+    /// Returns the result of `scope`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Mutex;
+    /// use stores::{Observable, Writable};
+    /// let observable = Observable::new(1);
+    /// let values = Mutex::new(Vec::new());
+    ///
+    /// observable.subscribe_scoped(
+    ///     |value| values.lock().unwrap().push(*value),
+    ///     || observable.set(2),
+    /// );
+    /// assert_eq!(*values.lock().unwrap(), vec![1, 2]);
+    /// ```
+    pub fn subscribe_scoped<'a, R>(
+        &self,
+        callback: impl Fn(&Value) + Send + Sync + 'a,
+        scope: impl FnOnce() -> R,
+    ) -> R {
+        let value = self.value.read().clone();
+        callback(&value);
+
+        let callback: Box<dyn Fn(&Value) + Send + Sync + 'a> = Box::new(callback);
+        // SAFETY: Only the lifetime is erased. The callback is only run while its slot is locked.
+        // The scope guard below takes it out of the slot under that lock before this function
+        // returns or unwinds, which waits for a running call to finish. So the callback is never
+        // used or dropped after `'a` ends, even if a notification still holds the registration.
+        let callback: Box<dyn Fn(&Value) + Send + Sync> = unsafe { mem::transmute(callback) };
+        let slot = Arc::new(lock::Mutex::new(Some(callback)));
+
+        let unsubscribe = self.subscribers.register(Callback::Subscriber(Box::new({
+            let slot = slot.clone();
+            move |value: &Value| {
+                if let Some(callback) = &*slot.lock_ignoring_poison() {
+                    callback(value);
+                }
+            }
+        })));
+
+        let _scope = Scope { unsubscribe, slot };
+        scope()
+    }
+
+    /// Creates a receiver that gets sent every value.
+    ///
+    /// The current value is sent immediately, followed by every subsequent change.
+    /// Once the receiver is dropped, the subscription is removed on the next change.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Writable};
+    /// let observable = Observable::new(1);
+    /// let receiver = observable.to_receiver();
+    ///
+    /// observable.set(2);
+    /// assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_receiver(&self) -> Receiver<Value> {
+        let (sender, receiver) = mpsc::channel();
+        let _ = sender.send(self.value.read().clone());
+
+        let _ = self.subscribers.register(Callback::Channel(sender));
+
+        receiver
+    }
+
+    /// Forwards every value into a channel, e.g. to handle it on a specific thread.
+    ///
+    /// The current value is sent immediately, followed by every subsequent change in order.
+    /// The thread owning the receiver drains it whenever it suits, e.g. in a GUI main loop.
+    /// Once the receiver is dropped, the subscription is removed on the next change.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::mpsc;
+    /// use stores::{Observable, Writable};
+    /// let observable = Observable::new(1);
+    /// let (sender, receiver) = mpsc::channel();
+    /// let unsubscribe = observable.subscribe_on_thread(sender);
+    ///
+    /// observable.set(2);
+    /// assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn subscribe_on_thread(&self, sender: Sender<Value>) -> Unsubscribe {
+        if sender.send(self.value.read().clone()).is_err() {
+            return Unsubscribe::new(|| {});
+        }
+
+        self.subscribers.register(Callback::Channel(sender))
+    }
+
+    /// Subscribes to value changes with a callback that runs on a background thread.
+    ///
+    /// Values are queued per subscription, so a slow callback does not block writers.
+    /// The callback receives the current value first, followed by every change in the order
+    /// they were emitted. The thread exits once unsubscribed or once the observable is dropped.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Writable};
+    /// let observable = Observable::new(1);
+    /// let unsubscribe = observable.subscribe_async(|value| println!("{}", value));
+    /// observable.set(2);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn subscribe_async(&self, callback: impl Fn(&Value) + Send + 'static) -> Unsubscribe {
+        let (sender, receiver) = mpsc::channel();
+        let _ = sender.send(self.value.read().clone());
+
+        let unsubscribe = self.subscribers.register(Callback::Channel(sender));
+        thread::spawn(move || receiver.iter().for_each(|value| callback(&value)));
+
+        unsubscribe
+    }
+
+    /// Subscribes to value changes with a callback that only receives the latest value.
+    ///
+    /// Like `subscribe_async`, but values are not queued. If the callback is still busy,
+    /// a new value replaces the pending one, so intermediate values are dropped and the callback
+    /// always catches up with the freshest value.
+    /// The thread exits once unsubscribed or once the observable is dropped.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Writable};
+    /// let observable = Observable::new(1);
+    /// let unsubscribe = observable.subscribe_latest(|value| println!("{}", value));
+    /// observable.set(2);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn subscribe_latest(&self, callback: impl Fn(&Value) + Send + 'static) -> Unsubscribe {
+        let mailbox = Arc::new(Mailbox::new());
+        let post = Post(mailbox.clone());
+        post.0.put(self.value.read().clone());
+
+        let unsubscribe =
+            self.subscribers
+                .register(Callback::Subscriber(Box::new(move |value: &Value| {
+                    post.0.put(value.clone())
+                })));
+
+        thread::spawn(move || {
+            while let Some(value) = mailbox.take() {
+                callback(&value);
+            }
+        });
+
+        unsubscribe
+    }
+
+    /// Creates a tokio broadcast receiver that gets sent every value.
+    ///
+    /// The current value is sent immediately, followed by every subsequent change.
+    /// Every call creates a separate channel with the given capacity, further receivers for the
+    /// same channel can be created via `resubscribe`. Receivers that fall behind by more than
+    /// `capacity` values lag as usual for broadcast channels.
+    /// Once all receivers of a channel are dropped, the subscription is removed on the next change.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Writable};
+    /// let observable = Observable::new(1);
+    /// let mut receiver = observable.to_broadcast(16);
+    ///
+    /// observable.set(2);
+    /// assert_eq!(receiver.try_recv(), Ok(1));
+    /// assert_eq!(receiver.try_recv(), Ok(2));
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub fn to_broadcast(&self, capacity: usize) -> broadcast::Receiver<Value> {
+        let (sender, receiver) = broadcast::channel(capacity);
+        let _ = sender.send(self.value.read().clone());
+
+        let _ = self.subscribers.register(Callback::Broadcast(sender));
+
+        receiver
+    }
+
+    /// Derives a new value by an async computation on every change.
+    ///
+    /// The computation is spawned on the current tokio runtime, see `AsyncDerived`.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Loadable, Observable, Readable};
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let id = Observable::new(1);
+    /// let user = id.map_async(|id| async move { format!("user {id}") });
+    /// assert_eq!(user.get(), Loadable::Loading);
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub fn map_async<Out, Fut>(
+        self: &Arc<Self>,
+        compute: impl Fn(Value) -> Fut + Send + Sync + 'static,
+    ) -> Arc<AsyncDerived<Out>>
+    where
+        Out: Clone + Send + Sync + 'static,
+        Fut: Future<Output = Out> + Send + 'static,
+    {
+        let source: Arc<dyn Emitter + Send + Sync> = self.clone();
+        AsyncDerived::new(&[source], {
+            let source = Arc::downgrade(self);
+            move || {
+                let value = source.upgrade().map(|source| source.get());
+                let future = value.map(&compute);
+                async move {
+                    match future {
+                        Some(future) => future.await,
+                        None => core::future::pending().await,
+                    }
+                }
+            }
+        })
+    }
+
+    /// Internal function to run all registered callbacks.
+    ///
+    /// Changes made by the callbacks themselves are notified once the current run completes.
+    /// Inside a transaction, the callbacks are only run once, when the transaction ends.
+    fn notify(&self) {
+        let value = self.value.read().clone();
+        self.subscribers.report(&value);
+
+        #[cfg(feature = "std")]
+        {
+            let store = Arc::as_ptr(self.subscribers.callbacks()) as usize;
+            let callbacks = self.subscribers.callbacks().clone();
+            transaction::defer(store, move || {
+                let _ = depth::notify(store, move |skipped| {
+                    Callback::notify_tracked(&callbacks, &value, store, skipped)
+                });
+            });
+        }
+        #[cfg(not(feature = "std"))]
+        Callback::notify_all(self.subscribers.callbacks(), &value);
+    }
+}
+
+impl<Value> Observable<Value>
+where
+    Value: PartialEq + Clone + Send + Sync + 'static,
+{
+    /// Creates a new observable that is updated by polling a function.
+    ///
+    /// The function is run once for the initial value and afterwards every `interval` on a
+    /// background thread. Values equal to the current one are not written again.
+    /// The thread stops as soon as the observable has been dropped.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use stores::Observable;
+    /// let start = Instant::now();
+    /// let elapsed = Observable::polling(Duration::from_millis(100), move || {
+    ///     start.elapsed().as_secs()
+    /// });
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn polling(interval: Duration, poll: impl Fn() -> Value + Send + 'static) -> Arc<Self> {
+        let instance = Self::new(poll());
+
+        thread::spawn({
+            let instance = Arc::downgrade(&instance);
+            move || {
+                loop {
+                    thread::sleep(interval);
+                    match instance.upgrade() {
+                        Some(instance) => instance.sync(&poll()),
+                        None => break,
+                    }
+                }
+            }
+        });
+
+        instance
+    }
+
+    /// Keeps two observables in sync in both directions.
+    ///
+    /// `b` takes over the value of `a` immediately, afterwards every change of one is written
+    /// to the other. Values equal to the current one are not written again, which stops the
+    /// change from bouncing back and forth. Both observables are only referenced weakly.
+    /// It returns a handle that can be called to tear down both directions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable, Writable};
+    /// let a = Observable::new(1);
+    /// let b = Observable::new(2);
+    /// let unbind = Observable::bind_two_way(&a, &b);
+    /// assert_eq!(b.get(), 1);
+    ///
+    /// b.set(3);
+    /// assert_eq!(a.get(), 3);
+    /// ```
+    pub fn bind_two_way(a: &Arc<Self>, b: &Arc<Self>) -> Unsubscribe {
+        let forward = a.subscribe_weak(b, Self::sync);
+        let backward = b.subscribe_weak(a, Self::sync);
+
+        Unsubscribe::new(move || {
+            forward();
+            backward();
+        })
+    }
+
+    /// Replaces the value with `new` if it currently equals `expected`.
+    ///
+    /// The comparison and the write happen under a single lock,
+    /// so concurrent writers can coordinate without losing updates.
+    /// Callbacks are only triggered if the value was swapped.
+    /// Returns whether the value was swapped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let observable = Observable::new(1);
+    ///
+    /// assert!(observable.compare_swap(&1, 2));
+    /// assert!(!observable.compare_swap(&1, 3));
+    /// assert_eq!(observable.get(), 2);
+    /// ```
+    pub fn compare_swap(&self, expected: &Value, new: Value) -> bool {
+        {
+            let mut value = self.value.write();
+            if *value != *expected {
+                return false;
+            }
+            *value = new;
+            self.version.fetch_add(1, Ordering::AcqRel);
+        }
+        self.notify();
+        true
+    }
+
+    /// Internal function to write a value unless it equals the current one.
+    fn sync(&self, value: &Value) {
+        if self.with(|current| current != value) {
+            self.set(value.clone());
+        }
+    }
+}
+
+impl<Item> Observable<Vec<Item>>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    /// Appends an item to the end of the vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::Observable;
+    /// # let observable = Observable::new(vec![1, 2]);
+    /// observable.push(3);
+    /// ```
+    pub fn push(&self, item: Item) {
+        self.modify(|value| value.push(item));
+    }
+
+    /// Removes the last item from the vector and returns it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::Observable;
+    /// # let observable = Observable::new(vec![1, 2]);
+    /// let last = observable.pop();
+    /// ```
+    pub fn pop(&self) -> Option<Item> {
+        let mut item = None;
+        self.modify(|value| item = value.pop());
+        item
+    }
+
+    /// Removes the item at the given index and returns it.
+    ///
+    /// Returns `None` without triggering callbacks if `index` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::Observable;
+    /// # let observable = Observable::new(vec![1, 2]);
+    /// assert_eq!(observable.remove(0), Some(1));
+    /// assert_eq!(observable.remove(5), None);
+    /// ```
+    pub fn remove(&self, index: usize) -> Option<Item> {
+        let item = {
+            let mut value = self.value.write();
+            if index >= value.len() {
+                return None;
+            }
+
+            self.version.fetch_add(1, Ordering::AcqRel);
+            value.remove(index)
+        };
+        self.notify();
+        Some(item)
+    }
+
+    /// Removes all items from the vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::Observable;
+    /// # let observable = Observable::new(vec![1, 2]);
+    /// observable.clear();
+    /// ```
+    pub fn clear(&self) {
+        self.modify(|value| value.clear());
+    }
+
+    /// Appends all items of the iterator to the vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::Observable;
+    /// # let observable = Observable::new(vec![1, 2]);
+    /// observable.extend([3, 4]);
+    /// ```
+    pub fn extend(&self, items: impl IntoIterator<Item = Item>) {
+        self.modify(|value| value.extend(items));
+    }
+
+    /// Creates a derived value holding the number of items.
+    ///
+    /// Only notifies when the number of items actually changes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let observable = Observable::new(vec![1, 2]);
+    /// let len = observable.len();
+    ///
+    /// observable.push(3);
+    /// assert_eq!(len.get(), 3);
+    /// ```
+    pub fn len(self: &Arc<Self>) -> Arc<Derived<usize>> {
+        self.derive_deduped(Vec::len)
+    }
+
+    /// Creates a derived value that is `true` while there are no items.
+    ///
+    /// Only notifies when the value actually changes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let observable = Observable::new(vec![1]);
+    /// let is_empty = observable.is_empty();
+    ///
+    /// observable.clear();
+    /// assert!(is_empty.get());
+    /// ```
+    pub fn is_empty(self: &Arc<Self>) -> Arc<Derived<bool>> {
+        self.derive_deduped(Vec::is_empty)
+    }
+
+    /// Derives a new vector by mapping every item.
+    ///
+    /// All items are mapped again whenever the vector changes.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let observable = Observable::new(vec![1, 2]);
+    /// let labels = observable.map_each(|item| item.to_string());
+    ///
+    /// observable.push(3);
+    /// assert_eq!(labels.get(), vec!["1", "2", "3"]);
+    /// ```
+    pub fn map_each<Out>(
+        self: &Arc<Self>,
+        map: impl Fn(&Item) -> Out + Send + Sync + 'static,
+    ) -> Arc<Derived<Vec<Out>>>
+    where
+        Out: Clone + Send + Sync + 'static,
+    {
+        Derived::new(&[as_emitter(self)], {
+            let value = self.clone();
+            move || value.with(|items| items.iter().map(&map).collect())
+        })
+    }
+}
+
+impl Observable<String> {
+    /// Creates a derived value holding the length of the string in bytes.
+    ///
+    /// Only notifies when the length actually changes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable, Writable};
+    /// let observable = Observable::new(String::from("a"));
+    /// let len = observable.len();
+    ///
+    /// observable.set(String::from("ab"));
+    /// assert_eq!(len.get(), 2);
+    /// ```
+    pub fn len(self: &Arc<Self>) -> Arc<Derived<usize>> {
+        self.derive_deduped(String::len)
+    }
+
+    /// Creates a derived value that is `true` while the string is empty.
+    ///
+    /// Only notifies when the value actually changes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable, Writable};
+    /// let observable = Observable::new(String::from("a"));
+    /// let is_empty = observable.is_empty();
+    ///
+    /// observable.set(String::new());
+    /// assert!(is_empty.get());
+    /// ```
+    pub fn is_empty(self: &Arc<Self>) -> Arc<Derived<bool>> {
+        self.derive_deduped(String::is_empty)
+    }
+}
+
+impl<Value> Observable<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    /// Creates a derived value holding the sum of both values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let a = Observable::new(1);
+    /// let b = Observable::new(2);
+    /// assert_eq!(a.plus(&b).get(), 3);
+    /// ```
+    pub fn plus(self: &Arc<Self>, other: &Arc<Self>) -> Arc<Derived<Value>>
+    where
+        Value: Add<Output = Value>,
+    {
+        self.combine(other, |a, b| a + b)
+    }
+
+    /// Creates a derived value holding the difference of both values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let a = Observable::new(3);
+    /// let b = Observable::new(2);
+    /// assert_eq!(a.minus(&b).get(), 1);
+    /// ```
+    pub fn minus(self: &Arc<Self>, other: &Arc<Self>) -> Arc<Derived<Value>>
+    where
+        Value: Sub<Output = Value>,
+    {
+        self.combine(other, |a, b| a - b)
+    }
+
+    /// Creates a derived value holding the product of both values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let a = Observable::new(2);
+    /// let b = Observable::new(3);
+    /// assert_eq!(a.times(&b).get(), 6);
+    /// ```
+    pub fn times(self: &Arc<Self>, other: &Arc<Self>) -> Arc<Derived<Value>>
+    where
+        Value: Mul<Output = Value>,
+    {
+        self.combine(other, |a, b| a * b)
+    }
+
+    /// Creates a derived value holding the value multiplied by a constant factor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let a = Observable::new(2);
+    /// assert_eq!(a.times_scalar(3).get(), 6);
+    /// ```
+    pub fn times_scalar(self: &Arc<Self>, factor: Value) -> Arc<Derived<Value>>
+    where
+        Value: Mul<Output = Value>,
+    {
+        Derived::new(&[as_emitter(self)], {
+            let value = self.clone();
+            move || value.get() * factor.clone()
+        })
+    }
+
+    /// Internal function to derive a value from both values.
+    fn combine(
+        self: &Arc<Self>,
+        other: &Arc<Self>,
+        combine: fn(Value, Value) -> Value,
+    ) -> Arc<Derived<Value>> {
+        Derived::new(&[self.clone(), other.clone()], {
+            let a = self.clone();
+            let b = other.clone();
+            move || combine(a.get(), b.get())
+        })
+    }
+
+    /// Internal function to derive a deduped value by reading the value by reference.
+    fn derive_deduped<Out>(self: &Arc<Self>, derive: fn(&Value) -> Out) -> Arc<Derived<Out>>
+    where
+        Out: PartialEq + Clone + Send + Sync + 'static,
+    {
+        Derived::new_deduped(&[as_emitter(self)], {
+            let value = self.clone();
+            move || value.with(derive)
+        })
+    }
+}
+
+impl Observable<bool> {
+    /// Flips the value.
+    ///
+    /// Calling this will trigger all registered callbacks once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let flag = Observable::new(false);
+    /// flag.toggle();
+    /// assert!(flag.get());
+    /// ```
+    pub fn toggle(&self) {
+        self.modify(|value| *value = !*value);
+    }
+
+    /// Sets the value to `true`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let flag = Observable::new(false);
+    /// flag.set_true();
+    /// assert!(flag.get());
+    /// ```
+    pub fn set_true(&self) {
+        self.set(true);
+    }
+
+    /// Sets the value to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let flag = Observable::new(true);
+    /// flag.set_false();
+    /// assert!(!flag.get());
+    /// ```
+    pub fn set_false(&self) {
+        self.set(false);
+    }
+
+    /// Creates a derived value that is `true` while both values are `true`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let a = Observable::new(true);
+    /// let b = Observable::new(false);
+    /// assert!(!a.and(&b).get());
+    /// ```
+    pub fn and(self: &Arc<Self>, other: &Arc<Self>) -> Arc<Derived<bool>> {
+        Derived::new(&[self.clone(), other.clone()], {
+            let a = self.clone();
+            let b = other.clone();
+            move || a.get() && b.get()
+        })
+    }
+
+    /// Creates a derived value that is `true` while any of both values is `true`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let a = Observable::new(true);
+    /// let b = Observable::new(false);
+    /// assert!(a.or(&b).get());
+    /// ```
+    pub fn or(self: &Arc<Self>, other: &Arc<Self>) -> Arc<Derived<bool>> {
+        Derived::new(&[self.clone(), other.clone()], {
+            let a = self.clone();
+            let b = other.clone();
+            move || a.get() || b.get()
+        })
+    }
+
+    /// Creates a derived value that is always the opposite of this value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let flag = Observable::new(true);
+    /// assert!(!flag.not().get());
+    /// ```
+    pub fn not(self: &Arc<Self>) -> Arc<Derived<bool>> {
+        Derived::new(&[as_emitter(self)], {
+            let flag = self.clone();
+            move || !flag.get()
+        })
+    }
+}
+
+impl<Item> Observable<Option<Item>>
+where
+    Item: Clone + Send + Sync + 'static,
+{
+    /// Sets the value to `Some(item)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable};
+    /// # let observable = Observable::new(None);
+    /// observable.set_some(1);
+    /// assert_eq!(observable.get(), Some(1));
+    /// ```
+    pub fn set_some(&self, item: Item) {
+        self.set(Some(item));
+    }
+
+    /// Takes the item out of the option, leaving `None` in its place.
+    ///
+    /// Callbacks are only triggered if there was an item to take,
+    /// taking from `None` leaves the value untouched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Observable, Readable};
+    /// # let observable = Observable::new(Some(1));
+    /// assert_eq!(observable.take(), Some(1));
+    /// assert_eq!(observable.get(), None);
+    /// ```
+    pub fn take(&self) -> Option<Item> {
+        let item = {
+            let mut value = self.value.write();
+            let item = value.take()?;
+            self.version.fetch_add(1, Ordering::AcqRel);
+            item
+        };
+        self.notify();
+        Some(item)
+    }
+
+    /// Returns the item or the given default if the value is `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::Observable;
+    /// # let observable = Observable::new(None);
+    /// assert_eq!(observable.get_or(1), 1);
+    /// ```
+    pub fn get_or(&self, default: Item) -> Item {
+        self.value.read().clone().unwrap_or(default)
+    }
+}
+
+impl<Item, Error> Observable<Loadable<Item, Error>>
+where
+    Item: Clone + Send + Sync + 'static,
+    Error: Clone + Send + Sync + 'static,
+{
+    /// Returns whether the item is being loaded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Loadable, Observable};
+    /// # let observable = Observable::new(Loadable::<i32>::Loading);
+    /// assert!(observable.is_loading());
+    /// ```
+    pub fn is_loading(&self) -> bool {
+        self.value.read().is_loading()
+    }
+
+    /// Returns the loaded item, if it is ready.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Loadable, Observable};
+    /// # let observable = Observable::new(Loadable::<i32>::Ready(1));
+    /// assert_eq!(observable.ready(), Some(1));
+    /// ```
+    pub fn ready(&self) -> Option<Item> {
+        self.value.read().ready().cloned()
+    }
+
+    /// Creates a new readable that maps the loaded item.
+    ///
+    /// While loading or after a failure, the map is skipped and the state is passed through.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use stores::{Loadable, Observable, Readable};
+    /// # let observable = Observable::new(Loadable::<i32>::Ready(1));
+    /// let doubled = observable.map_ready(|item| item * 2);
+    /// assert_eq!(doubled.get(), Loadable::Ready(2));
+    /// ```
+    pub fn map_ready<Out>(
+        self: &Arc<Self>,
+        map: impl Fn(&Item) -> Out + Send + Sync + 'static,
+    ) -> Arc<Mapped<Loadable<Out, Error>>>
+    where
+        Out: Clone + Send + Sync + 'static,
+    {
+        self.map(move |loadable| match loadable {
+            Loadable::Loading => Loadable::Loading,
+            Loadable::Ready(item) => Loadable::Ready(map(item)),
+            Loadable::Failed(error) => Loadable::Failed(error.clone()),
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Key, Item> Observable<HashMap<Key, Item>>
+where
+    Key: Eq + Hash + Clone + Send + Sync + 'static,
+    Item: Clone + Send + Sync + 'static,
+{
+    /// Inserts an item into the map and returns the previous item of that key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use stores::Observable;
+    /// # let observable = Observable::new(HashMap::new());
+    /// observable.insert("key", 1);
+    /// ```
+    pub fn insert(&self, key: Key, item: Item) -> Option<Item> {
+        let mut previous = None;
+        self.modify(|value| previous = value.insert(key, item));
+        previous
+    }
+
+    /// Removes a key from the map and returns its item.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use stores::Observable;
+    /// # let observable = Observable::new(HashMap::from([("key", 1)]));
+    /// let item = observable.remove(&"key");
+    /// ```
+    pub fn remove(&self, key: &Key) -> Option<Item> {
+        let mut item = None;
+        self.modify(|value| item = value.remove(key));
+        item
+    }
+}
+
+/// Read access to the value of an observable, see `Observable::read`.
+pub struct ReadGuard<'a, Value> {
+    guard: RwLockReadGuard<'a, Value>,
+}
+
+impl<Value> Deref for ReadGuard<'_, Value> {
+    type Target = Value;
+
+    fn deref(&self) -> &Value {
+        &self.guard
+    }
+}
+
+impl<Value> Debug for ReadGuard<'_, Value>
+where
+    Value: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ReadGuard").field(&*self.guard).finish()
+    }
+}
+
+/// Write access to the value of an observable, see `Observable::modify_guard`.
+pub struct ModifyGuard<'a, Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    observable: &'a Observable<Value>,
+    guard: Option<RwLockWriteGuard<'a, Value>>,
+}
+
+impl<Value> Deref for ModifyGuard<'_, Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    type Target = Value;
+
+    fn deref(&self) -> &Value {
+        self.guard
+            .as_ref()
+            .expect("the guard is only released on drop")
+    }
+}
+
+impl<Value> DerefMut for ModifyGuard<'_, Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn deref_mut(&mut self) -> &mut Value {
+        self.guard
+            .as_mut()
+            .expect("the guard is only released on drop")
+    }
+}
+
+impl<Value> Drop for ModifyGuard<'_, Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn drop(&mut self) {
+        // The version is bumped while the lock is still held, so concurrent updates retry.
+        // Callbacks read the value, so the lock has to be released before notifying.
+        self.observable.version.fetch_add(1, Ordering::AcqRel);
+        drop(self.guard.take());
+
+        // A panic while holding the guard poisons the lock, so notifying would panic again.
+        #[cfg(feature = "std")]
+        if std::thread::panicking() {
+            return;
+        }
+        self.observable.notify();
+    }
+}
+
+impl<Value> Debug for ModifyGuard<'_, Value>
+where
+    Value: Debug + Clone + Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ModifyGuard").field(&**self).finish()
+    }
+}
+
+/// Internal guard that removes a scoped callback and waits until it is no longer used.
+struct Scope<Value>
+where
+    Value: Send + Sync,
+{
+    unsubscribe: Unsubscribe,
+    slot: Arc<lock::Mutex<Option<ScopedCallback<Value>>>>,
+}
+
+/// Callback of a scoped subscription, whose lifetime has been erased.
+type ScopedCallback<Value> = Box<dyn Fn(&Value) + Send + Sync>;
+
+impl<Value> Drop for Scope<Value>
+where
+    Value: Send + Sync,
+{
+    fn drop(&mut self) {
+        (self.unsubscribe)();
+        // Blocks until a running call has finished, later calls find the slot empty.
+        // A panicking callback poisons the slot, which must not stop it from being emptied.
+        drop(self.slot.lock_ignoring_poison().take());
+    }
+}
+
+/// Internal single slot mailbox that only keeps the latest value.
+#[cfg(feature = "std")]
+struct Mailbox<Value> {
+    /// The pending value and whether the mailbox was closed.
+    state: Mutex<(Option<Value>, bool)>,
+    ready: Condvar,
+}
+
+#[cfg(feature = "std")]
+impl<Value> Mailbox<Value> {
+    /// Creates a new empty mailbox.
+    fn new() -> Self {
+        Self {
+            state: Mutex::new((None, false)),
+            ready: Condvar::new(),
+        }
+    }
+
+    /// Replaces the pending value.
+    fn put(&self, value: Value) {
+        self.state.lock().unwrap().0 = Some(value);
+        self.ready.notify_one();
+    }
+
+    /// Closes the mailbox, so `take` stops waiting for values.
+    fn close(&self) {
+        self.state.lock().unwrap().1 = true;
+        self.ready.notify_one();
+    }
+
+    /// Waits for the next value, returns `None` once the mailbox was closed.
+    fn take(&self) -> Option<Value> {
+        let state = self.state.lock().unwrap();
+        let mut state = self
+            .ready
+            .wait_while(state, |(value, closed)| value.is_none() && !*closed)
+            .unwrap();
+
+        state.0.take()
+    }
+}
+
+/// Internal handle that closes the mailbox once the callback is removed.
+#[cfg(feature = "std")]
+struct Post<Value>(Arc<Mailbox<Value>>);
+
+#[cfg(feature = "std")]
+impl<Value> Drop for Post<Value> {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+impl<Value> Emitter for Observable<Value>
+where
+    Value: Send + Sync + 'static,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.subscribers.listen(callback)
+    }
+}
+
+impl<Value> Readable<Value> for Observable<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn get(&self) -> Value {
+        self.value.read().clone()
+    }
+
+    fn subscribe_boxed(&self, callback: Box<dyn Fn(&Value) + Send + Sync>) -> Unsubscribe {
+        self.subscribe_with_opts(true, callback)
+    }
+}
+
+impl<Value> Writable<Value> for Observable<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn set(&self, value: Value) {
+        {
+            let mut current = self.value.write();
+            *current = value;
+            self.version.fetch_add(1, Ordering::AcqRel);
+        }
+        self.notify();
+    }
+
+    fn update_boxed(&self, updater: Box<dyn Fn(&Value) -> Value + Send + Sync>) {
+        // No lock is held while the updater runs, so it is retried on concurrent writes instead.
+        loop {
+            let (value, version) = {
+                let value = self.value.read();
+                (value.clone(), self.version.load(Ordering::Acquire))
+            };
+            let updated = updater(&value);
+
+            let mut current = self.value.write();
+            if self.version.load(Ordering::Acquire) == version {
+                *current = updated;
+                self.version.fetch_add(1, Ordering::AcqRel);
+                break;
+            }
+        }
+        self.notify();
+    }
+}
+
+impl<Value> ReadRef<Value> for Observable<Value>
+where
+    Value: Send + Sync,
+{
+    fn with<R>(&self, reader: impl FnOnce(&Value) -> R) -> R {
+        reader(&self.value.read())
+    }
+}
+
+impl<Value> Debug for Observable<Value>
+where
+    Value: Debug + Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Observable")
+            .field("value", &self.value.read())
+            .field("callbacks", &self.subscribers)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Mutex, thread};
+
+    use super::*;
+
+    #[test]
+    fn it_provides_getter() {
+        let observable = Observable::new(0);
+        assert_eq!(observable.get(), 0);
+    }
+
+    #[test]
+    fn it_provides_setters() {
+        let observable = Observable::new(0);
+
+        observable.set(1);
+        assert_eq!(observable.get(), 1);
+
+        observable.update(|value| value + 1);
+        assert_eq!(observable.get(), 2);
+    }
+
+    #[test]
+    fn it_triggers_emitter_on_change() {
+        let observable = Observable::new(0);
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = observable.listen({
+            let counter = counter.clone();
+            move || {
+                *counter.lock().unwrap() += 1;
+            }
+        });
+
+        assert_eq!(counter.lock().unwrap().clone(), 0);
+
+        observable.set(1);
+        assert_eq!(observable.get(), 1);
+    }
+
+    #[test]
+    fn it_unsubscribes_from_emitter() {
+        let observable = Observable::new(0);
+        let counter = Arc::new(Mutex::new(0));
+
+        let unsubscribe = observable.listen({
+            let counter = counter.clone();
+            move || {
+                *counter.lock().unwrap() += 1;
+            }
+        });
+
+        assert_eq!(counter.lock().unwrap().clone(), 0);
+
+        observable.set(1);
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+
+        unsubscribe();
+        observable.set(2);
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+    }
+
+    #[test]
+    fn it_provides_value_to_subscription() {
+        let observable = Observable::new(0);
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = observable.subscribe({
+            let counter = counter.clone();
+            move |value| {
+                *counter.lock().unwrap() = *value;
+            }
+        });
+
+        assert_eq!(observable.get(), 0);
+        assert_eq!(counter.lock().unwrap().clone(), 0);
+
+        observable.set(1);
+        assert_eq!(observable.get(), 1);
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+    }
+
+    #[test]
+    fn it_triggers_subscription_directly() {
+        let observable = Observable::new(0);
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = observable.subscribe({
+            let counter = counter.clone();
+            move |_| {
+                *counter.lock().unwrap() += 1;
+            }
+        });
+
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+
+        observable.set(1);
+        assert_eq!(counter.lock().unwrap().clone(), 2);
+    }
+
+    #[test]
+    fn it_unsubscribes_from_subscription() {
+        let observable = Observable::new(0);
+        let counter = Arc::new(Mutex::new(0));
+
+        let unsubscribe = observable.subscribe({
+            let counter = counter.clone();
+            move |_| {
+                *counter.lock().unwrap() += 1;
+            }
+        });
+
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+
+        unsubscribe();
+        observable.set(1);
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+    }
+
+    #[test]
+    fn it_works_in_threads() {
+        let observable = Observable::new(0);
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = observable.listen({
+            let counter = counter.clone();
+            move || {
+                *counter.lock().unwrap() += 1;
+            }
+        });
+
+        (0..10)
+            .map(|_| {
+                let observable = observable.clone();
+                thread::spawn({
+                    let observable = observable.clone();
+                    move || {
+                        observable.update(|value| value + 1);
+                    }
+                })
+            })
+            .for_each(|thread| thread.join().unwrap());
+
+        assert_eq!(observable.get(), 10);
+        assert_eq!(counter.lock().unwrap().clone(), 10);
+    }
+
+    #[test]
+    fn it_subscribes_from_threads() {
+        let observable = Observable::new(0);
+
+        (0..8)
+            .map(|thread| {
+                let observable = observable.clone();
+                thread::spawn(move || {
+                    (0..50).for_each(|_| {
+                        let _ = observable.subscribe_priority(thread, |_| {});
+                        let _ = observable.listen(|| {});
+                    });
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .for_each(|thread| thread.join().unwrap());
+
+        // Every subscription got an id of its own, so none of them replaced another one.
+        assert_eq!(observable.subscriber_count(), 800);
+    }
+
+    #[test]
+    fn it_counts_subscribers() {
+        let observable = Observable::new(0);
+        assert_eq!(observable.subscriber_count(), 0);
+
+        let unsubscribe_listener = observable.listen(|| {});
+        let unsubscribe_subscriber = observable.subscribe(|_| {});
+        assert_eq!(observable.subscriber_count(), 2);
+
+        unsubscribe_listener();
+        assert_eq!(observable.subscriber_count(), 1);
+
+        unsubscribe_subscriber();
+        assert_eq!(observable.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_modifies_in_place() {
+        let observable = Observable::new(vec![1]);
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = observable.listen({
+            let counter = counter.clone();
+            move || {
+                *counter.lock().unwrap() += 1;
+            }
+        });
+
+        observable.modify(|value| value.push(2));
+        assert_eq!(observable.get(), vec![1, 2]);
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+    }
+
+    #[test]
+    fn it_provides_vec_helpers() {
+        let observable = Observable::new(vec![1, 2]);
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = observable.listen({
+            let counter = counter.clone();
+            move || {
+                *counter.lock().unwrap() += 1;
+            }
+        });
+
+        observable.push(3);
+        assert_eq!(observable.get(), vec![1, 2, 3]);
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+
+        assert_eq!(observable.pop(), Some(3));
+        assert_eq!(observable.get(), vec![1, 2]);
+        assert_eq!(counter.lock().unwrap().clone(), 2);
+
+        assert_eq!(observable.remove(0), Some(1));
+        assert_eq!(observable.get(), vec![2]);
+        assert_eq!(counter.lock().unwrap().clone(), 3);
+
+        assert_eq!(observable.remove(1), None);
+        assert_eq!(observable.get(), vec![2]);
+        assert_eq!(counter.lock().unwrap().clone(), 3);
+
+        observable.extend([3, 4]);
+        assert_eq!(observable.get(), vec![2, 3, 4]);
+        assert_eq!(counter.lock().unwrap().clone(), 4);
+
+        observable.clear();
+        assert_eq!(observable.get(), Vec::<i32>::new());
+        assert_eq!(counter.lock().unwrap().clone(), 5);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_provides_map_helpers() {
+        let observable = Observable::new(HashMap::new());
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = observable.listen({
+            let counter = counter.clone();
+            move || {
+                *counter.lock().unwrap() += 1;
+            }
+        });
+
+        assert_eq!(observable.insert("a", 1), None);
+        assert_eq!(observable.insert("a", 2), Some(1));
+        assert_eq!(observable.get().get("a"), Some(&2));
+        assert_eq!(counter.lock().unwrap().clone(), 2);
+
+        assert_eq!(observable.remove(&"a"), Some(2));
+        assert!(observable.get().is_empty());
+        assert_eq!(counter.lock().unwrap().clone(), 3);
+    }
+
+    #[test]
+    fn it_provides_default() {
+        let observable = Observable::<String>::default();
+        assert_eq!(observable.get(), "");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_sends_values_to_receiver() {
+        let observable = Observable::new(0);
+        let receiver = observable.to_receiver();
+
+        let worker = thread::spawn(move || receiver.iter().take(4).collect::<Vec<_>>());
+
+        observable.set(1);
+        observable.set(2);
+        observable.set(3);
+
+        assert_eq!(worker.join().unwrap(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_removes_dropped_receivers() {
+        let observable = Observable::new(0);
+        let receiver = observable.to_receiver();
+        assert_eq!(observable.subscriber_count(), 1);
+
+        drop(receiver);
+        observable.set(1);
+        assert_eq!(observable.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_reads_non_clone_values_by_reference() {
+        struct Connection {
+            host: String,
+            port: Mutex<u16>,
+        }
+
+        let observable = Observable::new_uncloned(Connection {
+            host: String::from("localhost"),
+            port: Mutex::new(80),
+        });
+
+        assert_eq!(
+            observable.with(|connection| connection.host.clone()),
+            "localhost"
+        );
+
+        observable.with(|connection| *connection.port.lock().unwrap() = 8080);
+        assert_eq!(
+            observable.with(|connection| *connection.port.lock().unwrap()),
+            8080
+        );
+    }
+
+    #[test]
+    fn it_peeks_without_side_effects() {
+        let observable = Observable::new(1);
+        assert_eq!(observable.peek(), observable.get());
+
+        observable.set(2);
+        assert_eq!(observable.peek(), 2);
+        assert_eq!(*observable.peek_ref(), 2);
+        assert_eq!(observable.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_updates_conditionally() {
+        let observable = Observable::new(0);
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = observable.listen({
+            let counter = counter.clone();
+            move || {
+                *counter.lock().unwrap() += 1;
+            }
+        });
+
+        observable.update_maybe(|_| None);
+        assert_eq!(observable.get(), 0);
+        assert_eq!(counter.lock().unwrap().clone(), 0);
+
+        observable.update_maybe(|value| Some(value + 1));
+        assert_eq!(observable.get(), 1);
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+    }
+
+    #[test]
+    fn it_reads_inside_updater() {
+        let observable = Observable::new(1);
+
+        observable.update({
+            let observable = observable.clone();
+            move |value| value + observable.get()
+        });
+
+        assert_eq!(observable.get(), 2);
+    }
+
+    #[test]
+    fn it_initializes_lazily_once() {
+        let counter = Arc::new(Mutex::new(0));
+
+        let observable = Observable::new_with({
+            let counter = counter.clone();
+            move || {
+                *counter.lock().unwrap() += 1;
+                42
+            }
+        });
+
+        assert_eq!(observable.get(), 42);
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+    }
+
+    #[test]
+    fn it_resets_to_initial_value() {
+        let observable = Observable::new(1);
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = observable.subscribe({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+
+        observable.set(2);
+        observable.update(|value| value + 1);
+        observable.reset();
+
+        assert_eq!(observable.get(), 1);
+        assert_eq!(*values.lock().unwrap(), vec![1, 2, 3, 1]);
+    }
+
+    #[test]
+    fn it_removes_weak_subscription_of_dropped_owner() {
+        let observable = Observable::new(1);
+        let owner = Arc::new(Mutex::new(0));
+
+        let _ = observable.subscribe_weak(&owner, |owner, value| {
+            *owner.lock().unwrap() = *value;
+        });
+        assert_eq!(owner.lock().unwrap().clone(), 1);
+
+        observable.set(2);
+        assert_eq!(owner.lock().unwrap().clone(), 2);
+        assert_eq!(Arc::strong_count(&owner), 1);
+
+        drop(owner);
+        assert_eq!(observable.subscriber_count(), 1);
+
+        observable.set(3);
+        assert_eq!(observable.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_provides_option_helpers() {
+        let observable = Observable::new(None);
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = observable.listen({
+            let counter = counter.clone();
+            move || *counter.lock().unwrap() += 1
+        });
+
+        assert_eq!(observable.get_or(0), 0);
+
+        observable.set_some(1);
+        assert_eq!(observable.get_or(0), 1);
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+
+        assert_eq!(observable.take(), Some(1));
+        assert_eq!(observable.get(), None);
+        assert_eq!(counter.lock().unwrap().clone(), 2);
+    }
+
+    #[test]
+    fn it_does_not_notify_when_taking_none() {
+        let observable = Observable::<Option<i32>>::new(None);
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = observable.listen({
+            let counter = counter.clone();
+            move || *counter.lock().unwrap() += 1
+        });
+
+        assert_eq!(observable.take(), None);
+        assert_eq!(counter.lock().unwrap().clone(), 0);
+    }
+
+    #[test]
+    fn it_takes_the_item_only_once() {
+        let observable = Observable::new(Some(1));
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = observable.listen({
+            let counter = counter.clone();
+            move || *counter.lock().unwrap() += 1
+        });
+
+        let taken: Vec<_> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..4).map(|_| scope.spawn(|| observable.take())).collect();
+            handles
+                .into_iter()
+                .filter_map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        assert_eq!(taken, vec![1]);
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+    }
+
+    #[test]
+    fn it_subscribes_with_borrowed_callbacks() {
+        let observable = Observable::new(1);
+        let mut values = Vec::new();
+
+        {
+            let collected = Mutex::new(&mut values);
+            let count = observable.subscribe_scoped(
+                |value| collected.lock().unwrap().push(*value),
+                || {
+                    observable.set(2);
+                    observable.set(3);
+                    observable.subscriber_count()
+                },
+            );
+            assert_eq!(count, 1);
+        }
+
+        assert_eq!(observable.subscriber_count(), 0);
+        observable.set(4);
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn it_removes_scoped_callbacks_that_panicked() {
+        let observable = Observable::new(1);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            observable.subscribe_scoped(
+                |value| {
+                    if *value == 2 {
+                        panic!("failed");
+                    }
+                },
+                || observable.set(2),
+            )
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(observable.subscriber_count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_waits_for_running_scoped_callbacks() {
+        let observable = Observable::new(1);
+        let started = AtomicBool::new(false);
+        let finished = AtomicBool::new(false);
+
+        thread::scope(|threads| {
+            observable.subscribe_scoped(
+                |value| {
+                    if *value == 2 {
+                        started.store(true, Ordering::SeqCst);
+                        thread::sleep(Duration::from_millis(50));
+                        finished.store(true, Ordering::SeqCst);
+                    }
+                },
+                || {
+                    threads.spawn(|| observable.set(2));
+                    while !started.load(Ordering::SeqCst) {
+                        thread::yield_now();
+                    }
+                },
+            );
+
+            // The scope only ends once the notification in flight has finished.
+            assert!(finished.load(Ordering::SeqCst));
+        });
+        assert_eq!(observable.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_subscribes_with_opts() {
+        let observable = Observable::new(1);
+        let immediate = Arc::new(Mutex::new(Vec::new()));
+        let deferred = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = observable.subscribe_with_opts(true, {
+            let immediate = immediate.clone();
+            move |value| immediate.lock().unwrap().push(*value)
+        });
+        let _ = observable.subscribe_with_opts(false, {
+            let deferred = deferred.clone();
+            move |value| deferred.lock().unwrap().push(*value)
+        });
+        assert_eq!(*immediate.lock().unwrap(), vec![1]);
+        assert!(deferred.lock().unwrap().is_empty());
+
+        observable.set(2);
+        assert_eq!(*immediate.lock().unwrap(), vec![1, 2]);
+        assert_eq!(*deferred.lock().unwrap(), vec![2]);
+    }
+
+    #[test]
+    fn it_compares_and_swaps_across_threads() {
+        let observable = Observable::new(0);
+        let swaps = Arc::new(Mutex::new(0));
+
+        (0..10)
+            .map(|_| {
+                let observable = observable.clone();
+                let swaps = swaps.clone();
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        let current = observable.get();
+                        if observable.compare_swap(&current, current + 1) {
+                            *swaps.lock().unwrap() += 1;
+                        }
+                    }
+                })
+            })
+            .for_each(|thread| thread.join().unwrap());
+
+        assert_eq!(observable.get(), *swaps.lock().unwrap());
+        assert!(!observable.compare_swap(&-1, 0));
+    }
+
+    #[test]
+    fn it_binds_two_way() {
+        let a = Observable::new(1);
+        let b = Observable::new(2);
+        let changes = Arc::new(Mutex::new(0));
+
+        let _ = a.on_change({
+            let changes = changes.clone();
+            move |_| *changes.lock().unwrap() += 1
+        });
+
+        let unbind = Observable::bind_two_way(&a, &b);
+        assert_eq!(b.get(), 1);
+
+        a.set(3);
+        assert_eq!(b.get(), 3);
+
+        b.set(4);
+        assert_eq!(a.get(), 4);
+        assert_eq!(*changes.lock().unwrap(), 2);
+
+        unbind();
+        a.set(5);
+        assert_eq!(b.get(), 4);
+        assert_eq!(a.subscriber_count(), 1);
+        assert_eq!(b.subscriber_count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_polls_until_dropped() {
+        let counter = Arc::new(Mutex::new(0));
+        let observable = Observable::polling(Duration::from_millis(5), {
+            let counter = counter.clone();
+            move || {
+                let mut counter = counter.lock().unwrap();
+                *counter += 1;
+                *counter
+            }
+        });
+        assert_eq!(observable.get(), 1);
+
+        thread::sleep(Duration::from_millis(50));
+        assert!(observable.get() > 1);
+
+        drop(observable);
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "debug")]
+    fn it_lists_callbacks_in_debug_output() {
+        let observable = Observable::new(1);
+        let _ = observable.subscribe(|_| {});
+        let unsubscribe = observable.listen(|| {});
+        let _ = observable.listen(|| {});
+        unsubscribe();
+
+        let output = format!("{:?}", observable);
+        assert!(output.contains(r#"callbacks: {0: "Subscriber", 2: "Listener"}"#));
     }
-}
 
-impl<Value> Emitter for Observable<Value>
-where
-    Value: Clone + Send + Sync,
-{
-    fn listen(&self, callback: impl Fn() + Send + Sync + 'static) -> impl Fn() {
-        let callback = Box::new(callback);
-        let id = *self.counter.read().unwrap();
-        *self.counter.write().unwrap() += 1;
-
-        self.callbacks
-            .write()
-            .unwrap()
-            .insert(id, Callback::Listener(callback));
-        move || {
-            self.callbacks.write().unwrap().remove(&id);
-        }
+    #[tokio::test]
+    #[cfg(feature = "tokio")]
+    async fn it_broadcasts_values() {
+        let observable = Observable::new(0);
+        let mut receiver = observable.to_broadcast(8);
+        let mut other = receiver.resubscribe();
+
+        observable.set(1);
+        observable.set(2);
+        assert_eq!(receiver.recv().await, Ok(0));
+        assert_eq!(receiver.recv().await, Ok(1));
+        assert_eq!(receiver.recv().await, Ok(2));
+        assert_eq!(other.recv().await, Ok(1));
+
+        drop(receiver);
+        drop(other);
+        observable.set(3);
+        assert_eq!(observable.subscriber_count(), 0);
     }
-}
 
-impl<Value> Readable<Value> for Observable<Value>
-where
-    Value: Clone + Send + Sync,
-{
-    fn get(&self) -> Value {
-        self.value.read().unwrap().clone()
+    #[tokio::test]
+    #[cfg(feature = "tokio")]
+    async fn it_lags_slow_broadcast_receivers() {
+        let observable = Observable::new(0);
+        let mut receiver = observable.to_broadcast(2);
+
+        (1..=3).for_each(|value| observable.set(value));
+        assert_eq!(
+            receiver.recv().await,
+            Err(broadcast::error::RecvError::Lagged(2))
+        );
+        assert_eq!(receiver.recv().await, Ok(2));
     }
 
-    fn subscribe(&self, callback: impl Fn(&Value) + Send + Sync + 'static) -> impl Fn() {
-        let value = self.value.read().unwrap().clone();
-        callback(&value);
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_runs_async_subscribers_in_background() {
+        let observable = Observable::new(0);
+        let (sender, receiver) = mpsc::channel();
 
-        let callback = Box::new(callback);
-        let id = *self.counter.read().unwrap();
-        *self.counter.write().unwrap() += 1;
+        let unsubscribe = observable.subscribe_async(move |value| {
+            thread::sleep(Duration::from_millis(50));
+            sender.send(*value).unwrap();
+        });
+
+        let start = std::time::Instant::now();
+        observable.set(1);
+        observable.set(2);
+        assert!(start.elapsed() < Duration::from_millis(50));
+
+        let values: Vec<_> = receiver.iter().take(3).collect();
+        assert_eq!(values, vec![0, 1, 2]);
+
+        unsubscribe();
+        assert_eq!(observable.subscriber_count(), 0);
+        assert!(receiver.recv().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_only_delivers_latest_values() {
+        let observable = Observable::new(0);
+        let (sender, receiver) = mpsc::channel();
 
-        self.callbacks
-            .write()
-            .unwrap()
-            .insert(id, Callback::Subscriber(callback));
+        let unsubscribe = observable.subscribe_latest(move |value| {
+            thread::sleep(Duration::from_millis(20));
+            sender.send(*value).unwrap();
+        });
+
+        (1..=100).for_each(|value| observable.set(value));
 
-        move || {
-            self.callbacks.write().unwrap().remove(&id);
+        let mut values = Vec::new();
+        while values.last() != Some(&100) {
+            values.push(receiver.recv().unwrap());
         }
+        assert!(values.len() <= 3);
+
+        unsubscribe();
+        assert!(receiver.recv().is_err());
     }
-}
 
-impl<Value> Writable<Value> for Observable<Value>
-where
-    Value: Clone + Send + Sync,
-{
-    fn set(&self, value: Value) {
-        *self.value.write().unwrap() = value.clone();
-        self.notify();
+    #[test]
+    fn it_toggles_with_one_notification() {
+        let flag = Observable::new(false);
+        let (values, _) = flag.record();
+
+        flag.toggle();
+        flag.set_false();
+        flag.set_true();
+        assert_eq!(*values.lock().unwrap(), vec![false, true, false, true]);
     }
 
-    fn update(&self, updater: impl Fn(&Value) -> Value + Send + Sync + 'static) {
-        let value = updater(&self.value.read().unwrap());
-        self.set(value);
+    #[test]
+    fn it_combines_flags() {
+        let a = Observable::new(false);
+        let b = Observable::new(false);
+        let and = a.and(&b);
+        let or = a.or(&b);
+        let not = a.not();
+        assert!(!and.get() && !or.get() && not.get());
+
+        a.toggle();
+        assert!(!and.get() && or.get() && !not.get());
+
+        b.toggle();
+        assert!(and.get() && or.get());
+
+        a.toggle();
+        assert!(!and.get() && or.get() && not.get());
     }
-}
 
-impl<Value> Debug for Observable<Value>
-where
-    Value: Debug + Clone + Send + Sync,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Observable")
-            .field("value", &self.value.read().unwrap())
-            .field("callbacks", &self.callbacks.read().unwrap().len())
-            .finish()
+    #[test]
+    fn it_derives_arithmetic() {
+        let a = Observable::new(1);
+        let b = Observable::new(2);
+        let sum = a.plus(&b);
+        let difference = a.minus(&b);
+        let product = a.times(&b);
+        let doubled = a.times_scalar(2);
+        assert_eq!(sum.get(), 3);
+
+        a.set(5);
+        assert_eq!(sum.get(), 7);
+        assert_eq!(difference.get(), 3);
+        assert_eq!(product.get(), 10);
+        assert_eq!(doubled.get(), 10);
+
+        b.set(4);
+        assert_eq!(sum.get(), 9);
+        assert_eq!(difference.get(), 1);
+        assert_eq!(product.get(), 20);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::{sync::Mutex, thread};
+    #[test]
+    fn it_derives_collection_sizes() {
+        let observable = Observable::new(vec![1]);
+        let len = observable.len();
+        let is_empty = observable.is_empty();
+        let (lens, _) = len.record();
 
-    use super::*;
+        observable.push(2);
+        observable.modify(|value| value[0] = 3);
+        assert_eq!(len.get(), 2);
+        assert!(!is_empty.get());
+
+        observable.clear();
+        assert!(is_empty.get());
+        assert_eq!(*lens.lock().unwrap(), vec![1, 2, 0]);
+
+        let text = Observable::new(String::new());
+        let text_len = text.len();
+        text.set(String::from("abc"));
+        assert_eq!(text_len.get(), 3);
+        assert!(!text.is_empty().get());
+    }
 
     #[test]
-    fn it_provides_getter() {
+    fn it_does_not_lose_updates_under_contention() {
         let observable = Observable::new(0);
-        assert_eq!(observable.get(), 0);
+
+        (0..16)
+            .map(|_| {
+                let observable = observable.clone();
+                thread::spawn(move || {
+                    for _ in 0..500 {
+                        observable.update(|value| value + 1);
+                    }
+                })
+            })
+            .for_each(|thread| thread.join().unwrap());
+
+        assert_eq!(observable.get(), 16 * 500);
     }
 
     #[test]
-    fn it_provides_setters() {
-        let observable = Observable::new(0);
+    fn it_reads_through_a_guard() {
+        let observable = Observable::new((String::from("name"), vec![1, 2, 3]));
 
-        observable.set(1);
-        assert_eq!(observable.get(), 1);
+        let value = observable.read();
+        assert_eq!(value.0, "name");
+        assert_eq!(value.1, vec![1, 2, 3]);
+        drop(value);
 
-        observable.update(|value| value + 1);
-        assert_eq!(observable.get(), 2);
+        observable.modify(|value| value.1.push(4));
+        assert_eq!(observable.read().1.len(), 4);
     }
 
     #[test]
-    fn it_triggers_emitter_on_change() {
-        let observable = Observable::new(0);
-        let counter = Arc::new(Mutex::new(0));
+    fn it_blocks_writers_while_reading() {
+        use std::{sync::mpsc, time::Duration};
 
-        let _ = observable.listen({
-            let counter = counter.clone();
+        let observable = Observable::new(1);
+        let (sender, receiver) = mpsc::channel();
+
+        let guard = observable.read();
+        let writer = thread::spawn({
+            let observable = observable.clone();
             move || {
-                *counter.lock().unwrap() += 1;
+                observable.set(2);
+                sender.send(()).unwrap();
             }
         });
 
-        assert_eq!(counter.lock().unwrap().clone(), 0);
+        assert!(receiver.recv_timeout(Duration::from_millis(50)).is_err());
+        assert_eq!(*guard, 1);
+        drop(guard);
 
-        observable.set(1);
-        assert_eq!(observable.get(), 1);
+        receiver.recv().unwrap();
+        writer.join().unwrap();
+        assert_eq!(*observable.read(), 2);
     }
 
     #[test]
-    fn it_unsubscribes_from_emitter() {
-        let observable = Observable::new(0);
-        let counter = Arc::new(Mutex::new(0));
+    fn it_forks_independently() {
+        let observable = Observable::new(vec![1]);
+        let _ = observable.subscribe(|_| {});
 
-        let unsubscribe = observable.listen({
-            let counter = counter.clone();
-            move || {
-                *counter.lock().unwrap() += 1;
-            }
+        let fork = observable.fork();
+        assert_eq!(fork.get(), vec![1]);
+        assert_eq!(fork.subscriber_count(), 0);
+
+        fork.push(2);
+        observable.push(3);
+        assert_eq!(fork.get(), vec![1, 2]);
+        assert_eq!(observable.get(), vec![1, 3]);
+
+        fork.reset();
+        assert_eq!(fork.get(), vec![1]);
+    }
+
+    #[test]
+    fn it_subscribes_and_returns_the_current_value() {
+        let observable = Observable::new(1);
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let (value, unsubscribe) = observable.subscribe_get({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
         });
+        assert_eq!(value, 1);
 
-        assert_eq!(counter.lock().unwrap().clone(), 0);
+        observable.set(2);
+        unsubscribe();
+        observable.set(3);
+        assert_eq!(*values.lock().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn it_sets_silently() {
+        let observable = Observable::new(1);
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = observable.on_change({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+
+        observable.set_silent(2);
+        observable.set_silent(3);
+        assert_eq!(observable.get(), 3);
+        assert!(values.lock().unwrap().is_empty());
+
+        observable.notify_now();
+        assert_eq!(*values.lock().unwrap(), vec![3]);
+    }
+
+    #[test]
+    fn it_advances_the_version_on_every_write() {
+        let observable = Observable::new(1);
+        assert_eq!(observable.version(), 0);
 
         observable.set(1);
-        assert_eq!(counter.lock().unwrap().clone(), 1);
+        observable.update(|value| value + 1);
+        observable.modify(|value| *value += 1);
+        assert_eq!(observable.version(), 3);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn it_maps_asynchronously() {
+        let observable = Observable::new(1);
+        let doubled = observable.map_async(|value| async move { value * 2 });
 
-        unsubscribe();
         observable.set(2);
-        assert_eq!(counter.lock().unwrap().clone(), 1);
+        while doubled.get().is_loading() {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(doubled.get(), Loadable::Ready(4));
     }
 
     #[test]
-    fn it_provides_value_to_subscription() {
+    fn it_transitions_through_loadable_states() {
+        let observable = Observable::new(Loadable::<i32, &str>::Loading);
+        let doubled = observable.map_ready(|item| item * 2);
+        assert!(observable.is_loading());
+        assert_eq!(doubled.get(), Loadable::Loading);
+
+        observable.set(Loadable::Ready(2));
+        assert_eq!(observable.ready(), Some(2));
+        assert_eq!(doubled.get(), Loadable::Ready(4));
+
+        observable.set(Loadable::Failed("failed"));
+        assert_eq!(observable.ready(), None);
+        assert_eq!(doubled.get().failed(), Some(&"failed"));
+    }
+
+    #[test]
+    fn it_unwraps_unique_observables() {
+        let observable = Observable::new(String::from("value"));
+        assert_eq!(
+            Observable::try_unwrap(observable).ok(),
+            Some(String::from("value"))
+        );
+    }
+
+    #[test]
+    fn it_does_not_unwrap_shared_observables() {
+        let observable = Observable::new(1);
+        let weak = observable.downgrade();
+        let other = observable.clone();
+
+        let observable = Observable::try_unwrap(observable).unwrap_err();
+        assert!(Arc::ptr_eq(&observable, &other));
+        assert!(weak.upgrade().is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_queues_changes_from_own_callbacks() {
         let observable = Observable::new(0);
-        let counter = Arc::new(Mutex::new(0));
+        let values = Arc::new(Mutex::new(Vec::new()));
 
-        let _ = observable.subscribe({
-            let counter = counter.clone();
+        let _ = observable.on_change({
+            let observable = Arc::downgrade(&observable);
             move |value| {
-                *counter.lock().unwrap() = *value;
+                if *value > 10 {
+                    observable.upgrade().unwrap().set(10);
+                }
             }
         });
+        let _ = observable.on_change({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
 
-        assert_eq!(observable.get(), 0);
-        assert_eq!(counter.lock().unwrap().clone(), 0);
-
-        observable.set(1);
-        assert_eq!(observable.get(), 1);
-        assert_eq!(counter.lock().unwrap().clone(), 1);
+        observable.set(20);
+        assert_eq!(observable.get(), 10);
+        assert_eq!(*values.lock().unwrap(), vec![20, 10]);
     }
 
     #[test]
-    fn it_triggers_subscription_directly() {
+    #[cfg(feature = "std")]
+    fn it_terminates_when_callbacks_always_set() {
         let observable = Observable::new(0);
-        let counter = Arc::new(Mutex::new(0));
+        let calls = Arc::new(Mutex::new(0));
+        let values = Arc::new(Mutex::new(Vec::new()));
 
-        let _ = observable.subscribe({
-            let counter = counter.clone();
+        let _ = observable.on_change({
+            let observable = Arc::downgrade(&observable);
+            let calls = calls.clone();
             move |_| {
-                *counter.lock().unwrap() += 1;
+                *calls.lock().unwrap() += 1;
+                observable.upgrade().unwrap().set(1)
             }
         });
+        let _ = observable.on_change({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
 
-        assert_eq!(counter.lock().unwrap().clone(), 1);
+        observable.set(2);
+        assert_eq!(observable.get(), 1);
+        // Reaching the recursion limit would have run the writing callback 100 times.
+        assert_eq!(*calls.lock().unwrap(), 1);
+        assert_eq!(*values.lock().unwrap(), vec![2, 1]);
+    }
 
-        observable.set(1);
-        assert_eq!(counter.lock().unwrap().clone(), 2);
+    #[test]
+    fn it_maps_each_item() {
+        let observable = Observable::new(vec![1, 2]);
+        let labels = observable.map_each(|item| format!("#{item}"));
+        assert_eq!(labels.get(), vec!["#1", "#2"]);
+
+        observable.push(3);
+        observable.remove(0);
+        assert_eq!(labels.get(), vec!["#2", "#3"]);
+
+        observable.clear();
+        assert!(labels.get().is_empty());
     }
 
     #[test]
-    fn it_unsubscribes_from_subscription() {
-        let observable = Observable::new(0);
-        let counter = Arc::new(Mutex::new(0));
+    fn it_subscribes_until_the_callback_breaks() {
+        let observable = Observable::new(1);
+        let values = Arc::new(Mutex::new(Vec::new()));
 
-        let unsubscribe = observable.subscribe({
-            let counter = counter.clone();
-            move |_| {
-                *counter.lock().unwrap() += 1;
+        let _ = observable.subscribe_until({
+            let values = values.clone();
+            move |value| {
+                let mut values = values.lock().unwrap();
+                values.push(*value);
+                match values.len() {
+                    3 => ControlFlow::Break(()),
+                    _ => ControlFlow::Continue(()),
+                }
             }
         });
 
-        assert_eq!(counter.lock().unwrap().clone(), 1);
+        for value in 2..=5 {
+            observable.set(value);
+        }
+        assert_eq!(*values.lock().unwrap(), vec![1, 2, 3]);
+        assert_eq!(observable.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_does_not_register_when_breaking_immediately() {
+        let observable = Observable::new(1);
+        let _ = observable.subscribe_until(|_| ControlFlow::Break(()));
+        assert_eq!(observable.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_replaces_callbacks_with_the_same_key() {
+        let observable = Observable::new(1);
+        let first = Arc::new(Mutex::new(Vec::new()));
+        let second = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = observable.subscribe_keyed("render", {
+            let first = first.clone();
+            move |value| first.lock().unwrap().push(*value)
+        });
+        let unsubscribe = observable.subscribe_keyed("render", {
+            let second = second.clone();
+            move |value| second.lock().unwrap().push(*value)
+        });
+        let _ = observable.subscribe_keyed("other", |_| {});
+        assert_eq!(observable.subscriber_count(), 2);
+
+        observable.set(2);
+        assert_eq!(*first.lock().unwrap(), vec![1]);
+        assert_eq!(*second.lock().unwrap(), vec![1, 2]);
 
         unsubscribe();
+        assert_eq!(observable.subscriber_count(), 1);
+    }
+
+    #[test]
+    fn it_runs_callbacks_by_priority() {
+        let observable = Observable::new(0);
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let track = |name: &'static str| {
+            let order = order.clone();
+            move |_: &i32| order.lock().unwrap().push(name)
+        };
+
+        let _ = observable.subscribe(track("default"));
+        let _ = observable.subscribe_priority(10, track("late"));
+        let unsubscribe = observable.subscribe_priority(-5, track("early"));
+        let _ = observable.subscribe_priority(-10, track("earliest"));
+        order.lock().unwrap().clear();
+
         observable.set(1);
-        assert_eq!(counter.lock().unwrap().clone(), 1);
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec!["earliest", "early", "default", "late"]
+        );
+
+        unsubscribe();
+        assert_eq!(observable.subscriber_count(), 3);
     }
 
     #[test]
-    fn it_works_in_threads() {
+    #[cfg(feature = "std")]
+    fn it_forwards_values_into_a_channel() {
+        let observable = Observable::new(1);
+        let (sender, receiver) = mpsc::channel();
+        let _ = observable.subscribe_on_thread(sender);
+
+        thread::spawn({
+            let observable = observable.clone();
+            move || (2..=4).for_each(|value| observable.set(value))
+        })
+        .join()
+        .unwrap();
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+
+        drop(receiver);
+        observable.set(5);
+        assert_eq!(observable.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_replaces_atomically() {
         let observable = Observable::new(0);
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let observable = observable.clone();
+                thread::spawn(move || {
+                    (0..100)
+                        .map(|_| observable.replace_with(|value| value + 1))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let mut previous: Vec<_> = threads
+            .into_iter()
+            .flat_map(|thread| thread.join().unwrap())
+            .collect();
+        previous.sort();
+
+        assert_eq!(previous, (0..400).collect::<Vec<_>>());
+        assert_eq!(observable.get(), 400);
+    }
+
+    #[test]
+    fn it_notifies_once_the_modify_guard_is_dropped() {
+        let observable = Observable::new(vec![1]);
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = observable.on_change({
+            let values = values.clone();
+            move |value: &Vec<i32>| values.lock().unwrap().push(value.clone())
+        });
+
+        {
+            let mut value = observable.modify_guard();
+            value.push(2);
+            value.push(3);
+            assert!(values.lock().unwrap().is_empty());
+        }
+
+        assert_eq!(*values.lock().unwrap(), vec![vec![1, 2, 3]]);
+        assert_eq!(observable.version(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn it_does_not_notify_when_panicking_with_a_modify_guard() {
+        let observable = Observable::new(1);
         let counter = Arc::new(Mutex::new(0));
 
         let _ = observable.listen({
             let counter = counter.clone();
-            move || {
-                *counter.lock().unwrap() += 1;
-            }
+            move || *counter.lock().unwrap() += 1
         });
 
-        (0..10)
-            .map(|_| {
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let mut value = observable.modify_guard();
+            *value = 2;
+            panic!("failed");
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(counter.lock().unwrap().clone(), 0);
+    }
+
+    #[test]
+    fn it_does_not_lose_updates_next_to_modify_guards() {
+        let observable = Observable::new(0);
+        let threads: Vec<_> = (0..4)
+            .map(|index| {
                 let observable = observable.clone();
-                thread::spawn({
-                    let observable = observable.clone();
-                    move || {
-                        observable.update(|value| value + 1);
+                thread::spawn(move || {
+                    for _ in 0..200 {
+                        if index % 2 == 0 {
+                            *observable.modify_guard() += 1;
+                        } else {
+                            observable.update(|value| value + 1);
+                        }
                     }
                 })
             })
-            .for_each(|thread| thread.join().unwrap());
+            .collect();
 
-        assert_eq!(observable.get(), 10);
-        assert_eq!(counter.lock().unwrap().clone(), 10);
+        threads
+            .into_iter()
+            .for_each(|thread| thread.join().unwrap());
+        assert_eq!(observable.get(), 800);
     }
 }
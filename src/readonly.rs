@@ -0,0 +1,144 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::fmt::Debug;
+
+use crate::{Emitter, Observable, ReadRef, Readable, Unsubscribe};
+
+/// A read only view of another store.
+///
+/// Forwards all reads and change notifications to the wrapped store, but exposes no way to write
+/// to it. Useful for handing out internal state without allowing consumers to modify it.
+pub struct ReadOnly<Target> {
+    target: Arc<Target>,
+}
+
+impl<Target> ReadOnly<Target> {
+    /// Creates a new read only view of the target.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, ReadOnly, Readable};
+    /// let observable = Observable::new(1);
+    /// let readonly = ReadOnly::new(observable.clone());
+    /// assert_eq!(readonly.get(), 1);
+    /// ```
+    ///
+    /// The view cannot be written to:
+    ///
+    /// ```compile_fail
+    /// use stores::{Observable, ReadOnly, Writable};
+    /// let readonly = ReadOnly::new(Observable::new(1));
+    /// readonly.set(2);
+    /// ```
+    pub fn new(target: Arc<Target>) -> Self {
+        Self { target }
+    }
+}
+
+impl<Value> From<Arc<Observable<Value>>> for ReadOnly<Observable<Value>>
+where
+    Value: Send + Sync,
+{
+    fn from(target: Arc<Observable<Value>>) -> Self {
+        Self::new(target)
+    }
+}
+
+impl<Target> Clone for ReadOnly<Target> {
+    fn clone(&self) -> Self {
+        Self {
+            target: self.target.clone(),
+        }
+    }
+}
+
+impl<Target> Emitter for ReadOnly<Target>
+where
+    Target: Emitter,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.target.listen_boxed(callback)
+    }
+}
+
+impl<Value, Target> Readable<Value> for ReadOnly<Target>
+where
+    Value: Clone + Send + Sync,
+    Target: Readable<Value>,
+{
+    fn get(&self) -> Value {
+        self.target.get()
+    }
+
+    fn subscribe_boxed(&self, callback: Box<dyn Fn(&Value) + Send + Sync>) -> Unsubscribe {
+        self.target.subscribe_boxed(callback)
+    }
+}
+
+impl<Value, Target> ReadRef<Value> for ReadOnly<Target>
+where
+    Target: ReadRef<Value>,
+{
+    fn with<R>(&self, reader: impl FnOnce(&Value) -> R) -> R {
+        self.target.with(reader)
+    }
+}
+
+impl<Target> Debug for ReadOnly<Target>
+where
+    Target: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ReadOnly").field(&self.target).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::Writable;
+
+    use super::*;
+
+    #[test]
+    fn it_forwards_reads() {
+        let observable = Observable::new(1);
+        let readonly = ReadOnly::from(observable.clone());
+
+        assert_eq!(readonly.get(), 1);
+
+        observable.set(2);
+        assert_eq!(readonly.get(), 2);
+        assert_eq!(readonly.with(|value| value * 2), 4);
+    }
+
+    #[test]
+    fn it_forwards_notifications() {
+        let observable = Observable::new(1);
+        let readonly: ReadOnly<_> = observable.clone().into();
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = readonly.listen({
+            let counter = counter.clone();
+            move || {
+                *counter.lock().unwrap() += 1;
+            }
+        });
+        let unsubscribe = readonly.subscribe({
+            let counter = counter.clone();
+            move |value| {
+                *counter.lock().unwrap() += value;
+            }
+        });
+
+        assert_eq!(counter.lock().unwrap().clone(), 1);
+
+        observable.set(2);
+        assert_eq!(counter.lock().unwrap().clone(), 4);
+
+        unsubscribe();
+        observable.set(3);
+        assert_eq!(counter.lock().unwrap().clone(), 5);
+    }
+}
@@ -0,0 +1,228 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::fmt::Debug;
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{Emitter, Event, Observable, Unsubscribe, lock::RwLock};
+
+/// A collection of observables by key, e.g. for the state of many entities.
+///
+/// Listeners are notified whenever a key is added or removed,
+/// changes of the individual observables are not reported.
+pub struct Registry<Key, Value>
+where
+    Value: Send + Sync,
+{
+    entries: RwLock<HashMap<Key, Arc<Observable<Value>>>>,
+    changes: Arc<Event>,
+}
+
+impl<Key, Value> Registry<Key, Value>
+where
+    Key: Eq + Hash + Clone,
+    Value: Send + Sync,
+{
+    /// Creates a new empty registry.
+    ///
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Registry;
+    /// let registry = Registry::<u32, String>::new();
+    /// ```
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            entries: RwLock::new(HashMap::new()),
+            changes: Event::new(),
+        })
+    }
+
+    /// Returns the observable for the key, creating it if it does not exist yet.
+    ///
+    /// Listeners are notified if a new observable was created.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Readable, Registry};
+    /// let registry = Registry::new();
+    /// let user = registry.get_or_create(1, || String::from("user"));
+    /// assert_eq!(user.get(), "user");
+    /// ```
+    pub fn get_or_create(&self, key: Key, default: impl FnOnce() -> Value) -> Arc<Observable<Value>>
+    where
+        Value: Clone,
+    {
+        if let Some(entry) = self.get(&key) {
+            return entry;
+        }
+
+        let (entry, created) = {
+            let mut entries = self.entries.write();
+            match entries.get(&key) {
+                Some(entry) => (entry.clone(), false),
+                None => {
+                    let entry = Observable::new(default());
+                    entries.insert(key, entry.clone());
+                    (entry, true)
+                }
+            }
+        };
+
+        if created {
+            self.changes.dispatch();
+        }
+        entry
+    }
+
+    /// Returns the observable for the key, if it exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Registry;
+    /// let registry = Registry::<u32, String>::new();
+    /// assert!(registry.get(&1).is_none());
+    /// ```
+    pub fn get(&self, key: &Key) -> Option<Arc<Observable<Value>>> {
+        self.entries.read().get(key).cloned()
+    }
+
+    /// Removes the observable for the key and returns it, if it existed.
+    ///
+    /// Listeners are notified if an observable was removed.
+    /// Existing handles to the observable keep working, but it is no longer part of the registry.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Registry;
+    /// let registry = Registry::new();
+    /// registry.get_or_create(1, || 0);
+    /// assert!(registry.remove(&1).is_some());
+    /// ```
+    pub fn remove(&self, key: &Key) -> Option<Arc<Observable<Value>>> {
+        let entry = self.entries.write().remove(key);
+        if entry.is_some() {
+            self.changes.dispatch();
+        }
+        entry
+    }
+
+    /// Returns all keys in arbitrary order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Registry;
+    /// let registry = Registry::new();
+    /// registry.get_or_create(1, || 0);
+    /// assert_eq!(registry.keys(), vec![1]);
+    /// ```
+    pub fn keys(&self) -> Vec<Key> {
+        self.entries.read().keys().cloned().collect()
+    }
+
+    /// Returns the number of observables.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Registry;
+    /// let registry = Registry::<u32, String>::new();
+    /// assert_eq!(registry.len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.entries.read().len()
+    }
+
+    /// Returns whether the registry holds no observables.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Registry;
+    /// let registry = Registry::<u32, String>::new();
+    /// assert!(registry.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.entries.read().is_empty()
+    }
+}
+
+impl<Key, Value> Emitter for Registry<Key, Value>
+where
+    Value: Send + Sync,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.changes.listen_boxed(callback)
+    }
+}
+
+impl<Key, Value> Debug for Registry<Key, Value>
+where
+    Key: Debug,
+    Value: Debug + Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Registry")
+            .field("entries", &*self.entries.read())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{Readable, Writable};
+
+    use super::*;
+
+    #[test]
+    fn it_creates_entries_once() {
+        let registry = Registry::new();
+
+        let first = registry.get_or_create("a", || 1);
+        let second = registry.get_or_create("a", || 2);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(second.get(), 1);
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn it_subscribes_to_entries() {
+        let registry = Registry::new();
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = registry.get_or_create("a", || 1).on_change({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+
+        registry.get(&"a").unwrap().set(2);
+        registry.get_or_create("b", || 3).set(4);
+        assert_eq!(*values.lock().unwrap(), vec![2]);
+    }
+
+    #[test]
+    fn it_notifies_on_added_and_removed_keys() {
+        let registry = Registry::new();
+        let counter = Arc::new(Mutex::new(0));
+
+        let _ = registry.listen({
+            let counter = counter.clone();
+            move || *counter.lock().unwrap() += 1
+        });
+
+        registry.get_or_create(1, || 0);
+        registry.get_or_create(1, || 0).set(1);
+        assert_eq!(*counter.lock().unwrap(), 1);
+
+        assert!(registry.remove(&1).is_some());
+        assert!(registry.remove(&1).is_none());
+        assert_eq!(*counter.lock().unwrap(), 2);
+        assert!(registry.is_empty());
+    }
+}
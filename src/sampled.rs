@@ -0,0 +1,208 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::fmt::Debug;
+
+use crate::{Emitter, ReadRef, Readable, Subscribers, Unsubscribe, lock::RwLock};
+
+/// A readable observable value that samples another readable whenever a trigger fires.
+///
+/// Changes of the source are ignored until the trigger fires.
+pub struct Sampled<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    value: RwLock<Value>,
+    subscribers: Subscribers<Value>,
+}
+
+impl<Value> Sampled<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    /// Creates a new sampled value.
+    ///
+    /// The initial value is taken from the source.
+    /// Afterwards the current value of the source is captured and emitted every time the trigger
+    /// fires.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Event, Observable, Readable, Sampled, Writable};
+    /// let observable = Observable::new(1);
+    /// let trigger = Event::new();
+    /// let sampled = Sampled::new(&observable, &*trigger);
+    ///
+    /// observable.set(2);
+    /// assert_eq!(sampled.get(), 1);
+    ///
+    /// trigger.dispatch();
+    /// assert_eq!(sampled.get(), 2);
+    /// ```
+    pub fn new(
+        source: &Arc<impl Readable<Value> + Send + Sync + 'static>,
+        trigger: &(impl Emitter + ?Sized),
+    ) -> Arc<Self> {
+        let instance = Arc::new(Self {
+            value: RwLock::new(source.get()),
+            subscribers: Subscribers::new(),
+        });
+
+        let unsubscribe = trigger.listen_boxed(Box::new({
+            let instance = Arc::downgrade(&instance);
+            let source = source.clone();
+            move || {
+                if let Some(instance) = instance.upgrade() {
+                    *instance.value.write() = source.get();
+                    instance.notify();
+                }
+            }
+        }));
+        instance.subscribers.hold(unsubscribe);
+        instance.subscribers.keep(source.clone());
+
+        instance
+    }
+
+    /// Names the store, so its changes are reported to the global hook.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Event, Observable, Sampled};
+    /// let store = Sampled::new(&Observable::new(1), &*Event::new());
+    /// store.name("sampled");
+    /// ```
+    #[cfg(feature = "debug")]
+    pub fn name(&self, name: &str)
+    where
+        Value: Debug,
+    {
+        self.subscribers.name(name);
+    }
+
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Event, Observable, Sampled};
+    /// let store = Sampled::new(&Observable::new(1), &*Event::new());
+    /// assert_eq!(store.subscriber_count(), 0);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count()
+    }
+
+    /// Subscribes to value changes on behalf of a weakly referenced owner.
+    ///
+    /// Only a weak reference to `owner` is kept, so the subscription does not keep it alive.
+    /// The callback is run immediately and on every change as long as the owner exists.
+    /// Once the owner is dropped, the subscription removes itself on the next change.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use stores::{Event, Observable, Sampled};
+    /// let store = Sampled::new(&Observable::new(1), &*Event::new());
+    /// let owner = Arc::new(Mutex::new(0));
+    /// let unsubscribe = store.subscribe_weak(&owner, |owner, value| {
+    ///     *owner.lock().unwrap() = *value;
+    /// });
+    /// ```
+    pub fn subscribe_weak<Owner>(
+        &self,
+        owner: &Arc<Owner>,
+        callback: impl Fn(&Owner, &Value) + Send + Sync + 'static,
+    ) -> Unsubscribe
+    where
+        Owner: Send + Sync + 'static,
+    {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe_weak(value, owner, callback)
+    }
+
+    /// Internal function to run all registered callbacks.
+    fn notify(&self) {
+        let value = self.value.read().clone();
+        self.subscribers.notify(&value);
+    }
+}
+
+impl<Value> Emitter for Sampled<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.subscribers.listen(callback)
+    }
+}
+
+impl<Value> Readable<Value> for Sampled<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn get(&self) -> Value {
+        self.value.read().clone()
+    }
+
+    fn subscribe_boxed(&self, callback: Box<dyn Fn(&Value) + Send + Sync>) -> Unsubscribe {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe(value, callback)
+    }
+}
+
+impl<Value> ReadRef<Value> for Sampled<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    fn with<R>(&self, reader: impl FnOnce(&Value) -> R) -> R {
+        reader(&self.value.read())
+    }
+}
+
+impl<Value> Debug for Sampled<Value>
+where
+    Value: Debug + Clone + Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Sampled")
+            .field("value", &self.value.read())
+            .field("callbacks", &self.subscribers)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{Event, Observable, Writable};
+
+    use super::*;
+
+    #[test]
+    fn it_emits_latest_value_on_trigger() {
+        let observable = Observable::new(1);
+        let trigger = Event::new();
+        let sampled = Sampled::new(&observable, &*trigger);
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = sampled.on_change({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+
+        observable.set(2);
+        observable.set(3);
+        observable.set(4);
+        assert_eq!(sampled.get(), 1);
+        assert!(values.lock().unwrap().is_empty());
+
+        trigger.dispatch();
+        assert_eq!(sampled.get(), 4);
+        assert_eq!(*values.lock().unwrap(), vec![4]);
+    }
+}
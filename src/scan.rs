@@ -0,0 +1,169 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::fmt::Debug;
+
+use crate::{Emitter, ReadRef, Readable, Subscribers, Unsubscribe, lock::RwLock};
+
+/// A readable observable value that accumulates every value of another readable.
+///
+/// Works like `Iterator::scan`, but over all values the source has held.
+pub struct Scan<State>
+where
+    State: Clone + Send + Sync,
+{
+    value: RwLock<State>,
+    subscribers: Subscribers<State>,
+}
+
+impl<State> Scan<State>
+where
+    State: Clone + Send + Sync + 'static,
+{
+    /// Creates a new accumulated value.
+    ///
+    /// The current source value is folded into the initial state immediately,
+    /// every following source value whenever the source changes.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable, Scan, Writable};
+    /// let observable = Observable::new(1);
+    /// let total = Scan::new(&observable, 0, |total, value| total + value);
+    ///
+    /// observable.set(2);
+    /// assert_eq!(total.get(), 3);
+    /// ```
+    pub fn new<Source>(
+        source: &Arc<impl Readable<Source> + Send + Sync + 'static>,
+        initial: State,
+        fold: impl Fn(&State, &Source) -> State + Send + Sync + 'static,
+    ) -> Arc<Self>
+    where
+        Source: Clone + Send + Sync,
+    {
+        let instance = Arc::new(Self {
+            value: RwLock::new(initial),
+            subscribers: Subscribers::new(),
+        });
+
+        let unsubscribe = source.subscribe({
+            let instance = Arc::downgrade(&instance);
+            move |value| {
+                if let Some(instance) = instance.upgrade() {
+                    {
+                        let mut state = instance.value.write();
+                        *state = fold(&state, value);
+                    }
+                    instance.notify();
+                }
+            }
+        });
+        instance.subscribers.hold(unsubscribe);
+        instance.subscribers.keep(source.clone());
+
+        instance
+    }
+
+    /// Names the store, so its changes are reported to the global hook.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Scan};
+    /// let scan = Scan::new(&Observable::new(1), 0, |total, value| total + value);
+    /// scan.name("scan");
+    /// ```
+    #[cfg(feature = "debug")]
+    pub fn name(&self, name: &str)
+    where
+        State: Debug,
+    {
+        self.subscribers.name(name);
+    }
+
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Scan};
+    /// let scan = Scan::new(&Observable::new(1), 0, |total, value| total + value);
+    /// assert_eq!(scan.subscriber_count(), 0);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count()
+    }
+
+    /// Internal function to run all registered callbacks.
+    fn notify(&self) {
+        let value = self.value.read().clone();
+        self.subscribers.notify(&value);
+    }
+}
+
+impl<State> Emitter for Scan<State>
+where
+    State: Clone + Send + Sync + 'static,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.subscribers.listen(callback)
+    }
+}
+
+impl<State> Readable<State> for Scan<State>
+where
+    State: Clone + Send + Sync + 'static,
+{
+    fn get(&self) -> State {
+        self.value.read().clone()
+    }
+
+    fn subscribe_boxed(&self, callback: Box<dyn Fn(&State) + Send + Sync>) -> Unsubscribe {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe(value, callback)
+    }
+}
+
+impl<State> ReadRef<State> for Scan<State>
+where
+    State: Clone + Send + Sync,
+{
+    fn with<R>(&self, reader: impl FnOnce(&State) -> R) -> R {
+        reader(&self.value.read())
+    }
+}
+
+impl<State> Debug for Scan<State>
+where
+    State: Debug + Clone + Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Scan")
+            .field("value", &self.value.read())
+            .field("callbacks", &self.subscribers)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Observable, Writable};
+
+    use super::*;
+
+    #[test]
+    fn it_accumulates_values() {
+        let observable = Observable::new(1);
+        let history = Scan::new(&observable, alloc::vec::Vec::new(), |history, value| {
+            let mut history = history.clone();
+            history.push(*value);
+            history
+        });
+        assert_eq!(history.get(), vec![1]);
+
+        observable.set(2);
+        observable.set(3);
+        assert_eq!(history.get(), vec![1, 2, 3]);
+    }
+}
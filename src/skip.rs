@@ -0,0 +1,275 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    fmt::Debug,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use crate::{Emitter, ReadRef, Readable, Subscribers, Unsubscribe, lock::RwLock};
+
+/// A readable observable value that ignores changes of another readable until it is opened.
+///
+/// Created via `Readable::skip` or `Readable::skip_until`.
+pub struct Skip<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    value: RwLock<Value>,
+    subscribers: Subscribers<Value>,
+}
+
+impl<Value> Skip<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    /// Creates a new value that ignores the first `count` changes of the source.
+    ///
+    /// The initial value is taken from the source.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable, Skip, Writable};
+    /// let observable = Observable::new(1);
+    /// let skipped = Skip::new(&observable, 1);
+    ///
+    /// observable.set(2);
+    /// assert_eq!(skipped.get(), 1);
+    ///
+    /// observable.set(3);
+    /// assert_eq!(skipped.get(), 3);
+    /// ```
+    pub fn new(
+        source: &Arc<impl Readable<Value> + Send + Sync + 'static>,
+        count: usize,
+    ) -> Arc<Self> {
+        let skipped = AtomicUsize::new(0);
+        Self::from_gate(source, move || {
+            skipped.fetch_add(1, Ordering::AcqRel) >= count
+        })
+    }
+
+    /// Creates a new value that ignores all changes of the source until the trigger fires.
+    ///
+    /// The initial value is taken from the source.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Event, Observable, Readable, Skip, Writable};
+    /// let observable = Observable::new(1);
+    /// let trigger = Event::new();
+    /// let skipped = Skip::until(&observable, &*trigger);
+    ///
+    /// observable.set(2);
+    /// assert_eq!(skipped.get(), 1);
+    ///
+    /// trigger.dispatch();
+    /// observable.set(3);
+    /// assert_eq!(skipped.get(), 3);
+    /// ```
+    pub fn until(
+        source: &Arc<impl Readable<Value> + Send + Sync + 'static>,
+        trigger: &(impl Emitter + ?Sized),
+    ) -> Arc<Self> {
+        let open = Arc::new(AtomicBool::new(false));
+
+        let unsubscribe = trigger.listen_boxed(Box::new({
+            let open = open.clone();
+            move || open.store(true, Ordering::Release)
+        }));
+
+        let instance = Self::from_gate(source, move || open.load(Ordering::Acquire));
+        instance.subscribers.hold(unsubscribe);
+        instance
+    }
+
+    /// Internal function to take over every change of the source the gate lets through.
+    fn from_gate(
+        source: &Arc<impl Readable<Value> + Send + Sync + 'static>,
+        gate: impl Fn() -> bool + Send + Sync + 'static,
+    ) -> Arc<Self> {
+        let instance = Arc::new(Self {
+            value: RwLock::new(source.get()),
+            subscribers: Subscribers::new(),
+        });
+
+        let unsubscribe = source.on_change({
+            let instance = Arc::downgrade(&instance);
+            move |value| {
+                if let Some(instance) = instance.upgrade()
+                    && gate()
+                {
+                    *instance.value.write() = value.clone();
+                    instance.notify();
+                }
+            }
+        });
+        instance.subscribers.hold(unsubscribe);
+        instance.subscribers.keep(source.clone());
+
+        instance
+    }
+
+    /// Names the store, so its changes are reported to the global hook.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let store = Observable::new(1).skip(2);
+    /// store.name("skip");
+    /// ```
+    #[cfg(feature = "debug")]
+    pub fn name(&self, name: &str)
+    where
+        Value: Debug,
+    {
+        self.subscribers.name(name);
+    }
+
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let store = Observable::new(1).skip(2);
+    /// assert_eq!(store.subscriber_count(), 0);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count()
+    }
+
+    /// Subscribes to value changes on behalf of a weakly referenced owner.
+    ///
+    /// Only a weak reference to `owner` is kept, so the subscription does not keep it alive.
+    /// The callback is run immediately and on every change as long as the owner exists.
+    /// Once the owner is dropped, the subscription removes itself on the next change.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use stores::{Observable, Readable};
+    /// let store = Observable::new(1).skip(2);
+    /// let owner = Arc::new(Mutex::new(0));
+    /// let unsubscribe = store.subscribe_weak(&owner, |owner, value| {
+    ///     *owner.lock().unwrap() = *value;
+    /// });
+    /// ```
+    pub fn subscribe_weak<Owner>(
+        &self,
+        owner: &Arc<Owner>,
+        callback: impl Fn(&Owner, &Value) + Send + Sync + 'static,
+    ) -> Unsubscribe
+    where
+        Owner: Send + Sync + 'static,
+    {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe_weak(value, owner, callback)
+    }
+
+    /// Internal function to run all registered callbacks.
+    fn notify(&self) {
+        let value = self.value.read().clone();
+        self.subscribers.notify(&value);
+    }
+}
+
+impl<Value> Emitter for Skip<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.subscribers.listen(callback)
+    }
+}
+
+impl<Value> Readable<Value> for Skip<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn get(&self) -> Value {
+        self.value.read().clone()
+    }
+
+    fn subscribe_boxed(&self, callback: Box<dyn Fn(&Value) + Send + Sync>) -> Unsubscribe {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe(value, callback)
+    }
+}
+
+impl<Value> ReadRef<Value> for Skip<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    fn with<R>(&self, reader: impl FnOnce(&Value) -> R) -> R {
+        reader(&self.value.read())
+    }
+}
+
+impl<Value> Debug for Skip<Value>
+where
+    Value: Debug + Clone + Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Skip")
+            .field("value", &self.value.read())
+            .field("callbacks", &self.subscribers)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{Event, Observable, Writable};
+
+    use super::*;
+
+    #[test]
+    fn it_skips_first_changes() {
+        let observable = Observable::new(0);
+        let skipped = observable.skip(2);
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = skipped.on_change({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+
+        (1..=4).for_each(|value| observable.set(value));
+        assert_eq!(*values.lock().unwrap(), vec![3, 4]);
+    }
+
+    #[test]
+    fn it_skips_until_trigger() {
+        let observable = Observable::new(0);
+        let trigger = Event::new();
+        let skipped = observable.skip_until(&*trigger);
+
+        observable.set(1);
+        assert_eq!(skipped.get(), 0);
+
+        trigger.dispatch();
+        observable.set(2);
+        assert_eq!(skipped.get(), 2);
+    }
+
+    #[test]
+    fn it_unsubscribes_from_source_and_trigger_on_drop() {
+        let observable = Observable::new(0);
+        let trigger = Event::new();
+        let skipped = observable.skip_until(&*trigger);
+        assert_eq!(observable.subscriber_count(), 1);
+        assert_eq!(trigger.subscriber_count(), 1);
+
+        drop(skipped);
+        assert_eq!(observable.subscriber_count(), 0);
+        assert_eq!(trigger.subscriber_count(), 0);
+    }
+}
@@ -0,0 +1,214 @@
+use alloc::sync::Arc;
+
+use crate::{ReadRef, Readable, Scan};
+
+/// Running aggregates over all values a numeric store has held.
+///
+/// Usually tracked through a `Scan`, see `RunningStats::track`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunningStats<Value> {
+    min: Option<Value>,
+    max: Option<Value>,
+    sum: f64,
+    count: usize,
+}
+
+impl<Value> RunningStats<Value>
+where
+    Value: PartialOrd + Copy + Into<f64>,
+{
+    /// Creates empty aggregates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::RunningStats;
+    /// let stats = RunningStats::<i32>::new();
+    /// assert_eq!(stats.mean(), None);
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            min: None,
+            max: None,
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    /// Tracks the aggregates of every value of the source.
+    ///
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, RunningStats, Writable};
+    /// let observable = Observable::new(1);
+    /// let stats = RunningStats::track(&observable);
+    ///
+    /// observable.set(3);
+    /// assert_eq!(stats.mean(), Some(2.0));
+    /// ```
+    pub fn track(source: &Arc<impl Readable<Value> + Send + Sync + 'static>) -> Arc<Scan<Self>>
+    where
+        Value: Send + Sync + 'static,
+    {
+        Scan::new(source, Self::new(), Self::push)
+    }
+
+    /// Returns the aggregates including another value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::RunningStats;
+    /// let stats = RunningStats::new().push(&1).push(&2);
+    /// assert_eq!(stats.max(), Some(2));
+    /// ```
+    pub fn push(&self, value: &Value) -> Self {
+        let value = *value;
+        Self {
+            min: Some(match self.min {
+                Some(min) if min <= value => min,
+                _ => value,
+            }),
+            max: Some(match self.max {
+                Some(max) if max >= value => max,
+                _ => value,
+            }),
+            sum: self.sum + value.into(),
+            count: self.count + 1,
+        }
+    }
+
+    /// Returns the smallest value, if there was any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::RunningStats;
+    /// assert_eq!(RunningStats::new().push(&2).push(&1).min(), Some(1));
+    /// ```
+    pub fn min(&self) -> Option<Value> {
+        self.min
+    }
+
+    /// Returns the largest value, if there was any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::RunningStats;
+    /// assert_eq!(RunningStats::new().push(&2).push(&1).max(), Some(2));
+    /// ```
+    pub fn max(&self) -> Option<Value> {
+        self.max
+    }
+
+    /// Returns the arithmetic mean of all values, if there was any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::RunningStats;
+    /// assert_eq!(RunningStats::new().push(&2).push(&1).mean(), Some(1.5));
+    /// ```
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum / self.count as f64)
+    }
+
+    /// Returns the number of values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::RunningStats;
+    /// assert_eq!(RunningStats::new().push(&2).count(), 1);
+    /// ```
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl<Value> Default for RunningStats<Value>
+where
+    Value: PartialOrd + Copy + Into<f64>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Value> Scan<RunningStats<Value>>
+where
+    Value: PartialOrd + Copy + Into<f64> + Send + Sync + 'static,
+{
+    /// Returns the smallest value the source has held.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, RunningStats};
+    /// let stats = RunningStats::track(&Observable::new(1));
+    /// assert_eq!(stats.min(), Some(1));
+    /// ```
+    pub fn min(&self) -> Option<Value> {
+        self.with(RunningStats::min)
+    }
+
+    /// Returns the largest value the source has held.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, RunningStats};
+    /// let stats = RunningStats::track(&Observable::new(1));
+    /// assert_eq!(stats.max(), Some(1));
+    /// ```
+    pub fn max(&self) -> Option<Value> {
+        self.with(RunningStats::max)
+    }
+
+    /// Returns the arithmetic mean of all values the source has held.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, RunningStats};
+    /// let stats = RunningStats::track(&Observable::new(1));
+    /// assert_eq!(stats.mean(), Some(1.0));
+    /// ```
+    pub fn mean(&self) -> Option<f64> {
+        self.with(RunningStats::mean)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Observable, Writable};
+
+    use super::*;
+
+    #[test]
+    fn it_tracks_running_aggregates() {
+        let observable = Observable::new(4);
+        let stats = RunningStats::track(&observable);
+
+        for value in [2, 9, 5] {
+            observable.set(value);
+        }
+
+        assert_eq!(stats.min(), Some(2));
+        assert_eq!(stats.max(), Some(9));
+        assert_eq!(stats.mean(), Some(5.0));
+        assert_eq!(stats.get().count(), 4);
+    }
+
+    #[test]
+    fn it_starts_empty() {
+        let stats = RunningStats::<f32>::default();
+        assert_eq!(stats.min(), None);
+        assert_eq!(stats.max(), None);
+        assert_eq!(stats.mean(), None);
+    }
+}
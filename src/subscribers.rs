@@ -0,0 +1,201 @@
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+use core::{
+    any::Any,
+    fmt::Debug,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+#[cfg(feature = "debug")]
+use crate::devtools::Inspector;
+use crate::{Callback, Callbacks, Unsubscribe, lock::RwLock};
+
+/// Internal registry of the callbacks of a store, which every store forwards its subscriptions to.
+///
+/// It also holds the subscriptions of the store to its sources, which are removed once the store
+/// is dropped, and keeps the sources themselves alive. With the `debug` feature, the values of named stores are reported to the global
+/// hook whenever the callbacks are notified.
+pub(crate) struct Subscribers<Value>
+where
+    Value: Send + Sync,
+{
+    callbacks: Callbacks<Value>,
+    counter: AtomicUsize,
+    sources: RwLock<Vec<Unsubscribe>>,
+    upstream: RwLock<Vec<Arc<dyn Any + Send + Sync>>>,
+    #[cfg(feature = "debug")]
+    inspector: Inspector<Value>,
+}
+
+impl<Value> Subscribers<Value>
+where
+    Value: Send + Sync,
+{
+    /// Creates a new registry without any callbacks.
+    pub(crate) fn new() -> Self {
+        Self {
+            callbacks: Arc::new(RwLock::new(BTreeMap::new())),
+            counter: AtomicUsize::new(0),
+            sources: RwLock::new(Vec::new()),
+            upstream: RwLock::new(Vec::new()),
+            #[cfg(feature = "debug")]
+            inspector: Inspector::new(),
+        }
+    }
+
+    /// Returns the registered callbacks, which are shared with the unsubscribe handles.
+    pub(crate) fn callbacks(&self) -> &Callbacks<Value> {
+        &self.callbacks
+    }
+
+    /// Returns a new id to register a callback with, which is unique even across threads.
+    pub(crate) fn next_id(&self) -> usize {
+        self.counter.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Keeps the subscription of the store to one of its sources until the store is dropped.
+    pub(crate) fn hold(&self, unsubscribe: Unsubscribe) {
+        self.sources.write().push(unsubscribe);
+    }
+
+    /// Keeps a source alive until the store is dropped.
+    ///
+    /// Sources only reference the store weakly, so this keeps chained stores working even
+    /// if the intermediate stores are not referenced anywhere else.
+    pub(crate) fn keep(&self, source: Arc<dyn Any + Send + Sync>) {
+        self.upstream.write().push(source);
+    }
+
+    /// Returns the number of registered callbacks.
+    pub(crate) fn count(&self) -> usize {
+        self.callbacks.read().len()
+    }
+
+    /// Removes all registered callbacks.
+    pub(crate) fn clear(&self) {
+        self.callbacks.write().clear();
+    }
+
+    /// Runs all registered callbacks with the given value.
+    pub(crate) fn notify(&self, value: &Value)
+    where
+        Value: Clone,
+    {
+        self.report(value);
+        Callback::notify_all(&self.callbacks, value);
+    }
+
+    /// Reports the value to the global hook if the store is named.
+    pub(crate) fn report(&self, value: &Value) {
+        #[cfg(feature = "debug")]
+        self.inspector.report(value);
+        #[cfg(not(feature = "debug"))]
+        let _ = value;
+    }
+
+    /// Names the store, so its changes are reported to the global hook.
+    #[cfg(feature = "debug")]
+    pub(crate) fn name(&self, name: &str)
+    where
+        Value: Debug,
+    {
+        self.inspector.name(name);
+    }
+}
+
+impl<Value> Subscribers<Value>
+where
+    Value: Send + Sync + 'static,
+{
+    /// Registers the callback by priority and id, replacing any other one with the same key.
+    pub(crate) fn insert(&self, key: (i32, usize), callback: Callback<Value>) -> Unsubscribe {
+        self.callbacks.write().insert(key, Arc::new(callback));
+        Unsubscribe::remove(&self.callbacks, key)
+    }
+
+    /// Registers the callback with the default priority.
+    pub(crate) fn register(&self, callback: Callback<Value>) -> Unsubscribe {
+        self.insert((0, self.next_id()), callback)
+    }
+
+    /// Registers a callback that is run on every change.
+    pub(crate) fn listen(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.register(Callback::Listener(callback))
+    }
+
+    /// Registers a callback that gets every new value, after running it with the current `value`.
+    pub(crate) fn subscribe(
+        &self,
+        value: Value,
+        callback: Box<dyn Fn(&Value) + Send + Sync>,
+    ) -> Unsubscribe {
+        callback(&value);
+        self.register(Callback::Subscriber(callback))
+    }
+
+    /// Registers a callback on behalf of a weakly referenced owner, after running it with `value`.
+    ///
+    /// The callback removes itself on the next change once the owner was dropped.
+    pub(crate) fn subscribe_weak<Owner>(
+        &self,
+        value: Value,
+        owner: &Arc<Owner>,
+        callback: impl Fn(&Owner, &Value) + Send + Sync + 'static,
+    ) -> Unsubscribe
+    where
+        Owner: Send + Sync + 'static,
+    {
+        callback(owner, &value);
+
+        let owner = Arc::downgrade(owner);
+        let callback = Box::new(move |value: &Value| match owner.upgrade() {
+            Some(owner) => {
+                callback(&owner, value);
+                true
+            }
+            None => false,
+        });
+        self.register(Callback::Weak(callback))
+    }
+}
+
+impl<Value> Drop for Subscribers<Value>
+where
+    Value: Send + Sync,
+{
+    fn drop(&mut self) {
+        self.sources
+            .read()
+            .iter()
+            .for_each(|unsubscribe| unsubscribe());
+    }
+}
+
+/// Shows the number of callbacks, or with the `debug` feature the kind of every callback by id.
+impl<Value> Debug for Subscribers<Value>
+where
+    Value: Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let callbacks = self.callbacks.read();
+
+        #[cfg(not(feature = "debug"))]
+        return callbacks.len().fmt(f);
+
+        #[cfg(feature = "debug")]
+        return f
+            .debug_map()
+            .entries(callbacks.iter().map(|((_, id), callback)| {
+                let kind = match **callback {
+                    Callback::Subscriber(_) => "Subscriber",
+                    Callback::Listener(_) => "Listener",
+                    Callback::Weak(_) => "Weak",
+                    #[cfg(feature = "std")]
+                    Callback::Channel(_) => "Channel",
+                    #[cfg(feature = "tokio")]
+                    Callback::Broadcast(_) => "Broadcast",
+                };
+                (id, kind)
+            }))
+            .finish();
+    }
+}
@@ -0,0 +1,296 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    fmt::Debug,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{Emitter, Observable, ReadRef, Readable, Subscribers, Unsubscribe, lock::RwLock};
+
+/// A readable observable value that mirrors whichever observable a selector currently picks.
+///
+/// Whenever the selector changes, the previous source is unsubscribed from and the newly
+/// selected one is followed instead. Neither the selector nor the sources keep the switch alive.
+pub struct Switch<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    value: RwLock<Value>,
+    selection: RwLock<Option<Unsubscribe>>,
+    source: RwLock<Option<Unsubscribe>>,
+    generation: AtomicUsize,
+    subscribers: Subscribers<Value>,
+}
+
+impl<Value> Switch<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    /// Creates a new switch between observables.
+    ///
+    /// The resolver maps the current selection to the observable to mirror.
+    /// It is run immediately and whenever the selector changes.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable, Switch, Writable};
+    /// let celsius = Observable::new(20);
+    /// let fahrenheit = Observable::new(68);
+    /// let unit = Observable::new('C');
+    /// let temperature = Switch::new(&unit, {
+    ///     let celsius = celsius.clone();
+    ///     let fahrenheit = fahrenheit.clone();
+    ///     move |unit| match unit {
+    ///         'C' => celsius.clone(),
+    ///         _ => fahrenheit.clone(),
+    ///     }
+    /// });
+    /// assert_eq!(temperature.get(), 20);
+    ///
+    /// unit.set('F');
+    /// assert_eq!(temperature.get(), 68);
+    /// ```
+    pub fn new<Key>(
+        selector: &Arc<impl Readable<Key>>,
+        resolve: impl Fn(&Key) -> Arc<Observable<Value>> + Send + Sync + 'static,
+    ) -> Arc<Self>
+    where
+        Key: Clone + Send + Sync,
+    {
+        let source = resolve(&selector.get());
+        let instance = Arc::new(Self {
+            value: RwLock::new(source.get()),
+            selection: RwLock::new(None),
+            source: RwLock::new(None),
+            generation: AtomicUsize::new(0),
+            subscribers: Subscribers::new(),
+        });
+        instance.follow(&source);
+
+        let selection = selector.on_change({
+            let instance = Arc::downgrade(&instance);
+            move |key| {
+                if let Some(instance) = instance.upgrade() {
+                    instance.follow(&resolve(key));
+                }
+            }
+        });
+        *instance.selection.write() = Some(selection);
+
+        instance
+    }
+
+    /// Names the store, so its changes are reported to the global hook.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Switch};
+    /// let source = Observable::new(1);
+    /// let switch = Switch::new(&Observable::new(()), move |_| source.clone());
+    /// switch.name("switch");
+    /// ```
+    #[cfg(feature = "debug")]
+    pub fn name(&self, name: &str)
+    where
+        Value: Debug,
+    {
+        self.subscribers.name(name);
+    }
+
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Switch};
+    /// let source = Observable::new(1);
+    /// let switch = Switch::new(&Observable::new(()), move |_| source.clone());
+    /// assert_eq!(switch.subscriber_count(), 0);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count()
+    }
+
+    /// Internal function to stop following the previous source and mirror the given one instead.
+    ///
+    /// No lock is held while subscribing, since that already runs the callbacks, which may
+    /// change the selector again. The latest selection wins in that case.
+    fn follow(self: &Arc<Self>, source: &Arc<Observable<Value>>) {
+        let generation = self.generation.fetch_add(1, Ordering::AcqRel) + 1;
+
+        // Subscribing mirrors the current value of the new source right away.
+        let subscription = source.subscribe({
+            let instance = Arc::downgrade(self);
+            move |value| {
+                if let Some(instance) = instance.upgrade() {
+                    if instance.generation.load(Ordering::Acquire) != generation {
+                        return;
+                    }
+
+                    *instance.value.write() = value.clone();
+                    instance.notify();
+                }
+            }
+        });
+
+        let previous = {
+            let mut current = self.source.write();
+            match self.generation.load(Ordering::Acquire) == generation {
+                true => current.replace(subscription),
+                false => Some(subscription),
+            }
+        };
+
+        if let Some(unsubscribe) = previous {
+            unsubscribe();
+        }
+    }
+
+    /// Internal function to run all registered callbacks.
+    fn notify(&self) {
+        let value = self.value.read().clone();
+        self.subscribers.notify(&value);
+    }
+}
+
+impl<Value> Emitter for Switch<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.subscribers.listen(callback)
+    }
+}
+
+impl<Value> Readable<Value> for Switch<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn get(&self) -> Value {
+        self.value.read().clone()
+    }
+
+    fn subscribe_boxed(&self, callback: Box<dyn Fn(&Value) + Send + Sync>) -> Unsubscribe {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe(value, callback)
+    }
+}
+
+impl<Value> ReadRef<Value> for Switch<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    fn with<R>(&self, reader: impl FnOnce(&Value) -> R) -> R {
+        reader(&self.value.read())
+    }
+}
+
+impl<Value> Drop for Switch<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    fn drop(&mut self) {
+        [self.selection.write().take(), self.source.write().take()]
+            .into_iter()
+            .flatten()
+            .for_each(|unsubscribe| unsubscribe());
+    }
+}
+
+impl<Value> Debug for Switch<Value>
+where
+    Value: Debug + Clone + Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Switch")
+            .field("value", &self.value.read())
+            .field("callbacks", &self.subscribers)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::Writable;
+
+    use super::*;
+
+    #[test]
+    fn it_follows_the_selected_source() {
+        let sources = [Observable::new(1), Observable::new(10)];
+        let selector = Observable::new(0);
+        let switch = Switch::new(&selector, {
+            let sources = sources.clone();
+            move |index: &usize| sources[*index].clone()
+        });
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = switch.subscribe({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+
+        sources[0].set(2);
+        selector.set(1);
+        sources[0].set(3);
+        sources[1].set(11);
+
+        assert_eq!(switch.get(), 11);
+        assert_eq!(*values.lock().unwrap(), vec![1, 2, 10, 11]);
+    }
+
+    #[test]
+    fn it_unsubscribes_from_previous_sources() {
+        let sources = [Observable::new(1), Observable::new(2)];
+        let selector = Observable::new(0);
+        let switch = Switch::new(&selector, {
+            let sources = sources.clone();
+            move |index: &usize| sources[*index].clone()
+        });
+        assert_eq!(sources[0].subscriber_count(), 1);
+
+        selector.set(1);
+        assert_eq!(sources[0].subscriber_count(), 0);
+        assert_eq!(sources[1].subscriber_count(), 1);
+
+        drop(switch);
+        assert_eq!(selector.subscriber_count(), 0);
+        assert_eq!(sources[1].subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_follows_selections_made_by_its_own_callbacks() {
+        let sources = [Observable::new(1), Observable::new(2), Observable::new(3)];
+        let selector = Observable::new(0);
+        let switch = Switch::new(&selector, {
+            let sources = sources.clone();
+            move |index: &usize| sources[*index].clone()
+        });
+
+        // Selecting the second source immediately moves on to the third one.
+        let _ = switch.on_change({
+            let selector = selector.clone();
+            move |value| {
+                if *value == 2 {
+                    selector.set(2);
+                }
+            }
+        });
+
+        selector.set(1);
+        assert_eq!(switch.get(), 3);
+        assert_eq!(selector.get(), 2);
+        assert_eq!(
+            sources.each_ref().map(|source| source.subscriber_count()),
+            [0, 0, 1]
+        );
+
+        sources[1].set(20);
+        sources[2].set(30);
+        assert_eq!(switch.get(), 30);
+    }
+}
@@ -0,0 +1,238 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    fmt::Debug,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{Emitter, ReadRef, Readable, Subscribers, Unsubscribe, lock::RwLock};
+
+/// A readable observable value that only takes over the first changes of another readable.
+///
+/// Once the limit is reached, it unsubscribes from the source and stays unchanged.
+pub struct Take<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    value: RwLock<Value>,
+    source: RwLock<Option<Unsubscribe>>,
+    remaining: AtomicUsize,
+    subscribers: Subscribers<Value>,
+}
+
+impl<Value> Take<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    /// Creates a new value that takes over the first `count` changes of the source.
+    ///
+    /// The initial value is taken from the source.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable, Take, Writable};
+    /// let observable = Observable::new(1);
+    /// let taken = Take::new(&observable, 1);
+    ///
+    /// observable.set(2);
+    /// observable.set(3);
+    /// assert_eq!(taken.get(), 2);
+    /// ```
+    pub fn new(
+        source: &Arc<impl Readable<Value> + Send + Sync + 'static>,
+        count: usize,
+    ) -> Arc<Self> {
+        let instance = Arc::new(Self {
+            value: RwLock::new(source.get()),
+            source: RwLock::new(None),
+            remaining: AtomicUsize::new(count),
+            subscribers: Subscribers::new(),
+        });
+
+        let unsubscribe = source.on_change({
+            let instance = Arc::downgrade(&instance);
+            move |value| {
+                let Some(instance) = instance.upgrade() else {
+                    return;
+                };
+
+                let taken = instance.remaining.fetch_update(
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                    |remaining| remaining.checked_sub(1),
+                );
+
+                if let Ok(remaining) = taken {
+                    *instance.value.write() = value.clone();
+                    instance.notify();
+
+                    if remaining == 1 {
+                        instance.unsubscribe();
+                    }
+                }
+            }
+        });
+
+        *instance.source.write() = Some(unsubscribe);
+        instance.subscribers.keep(source.clone());
+        if instance.remaining.load(Ordering::Acquire) == 0 {
+            instance.unsubscribe();
+        }
+
+        instance
+    }
+
+    /// Names the store, so its changes are reported to the global hook.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Take};
+    /// let store = Take::new(&Observable::new(1), 2);
+    /// store.name("take");
+    /// ```
+    #[cfg(feature = "debug")]
+    pub fn name(&self, name: &str)
+    where
+        Value: Debug,
+    {
+        self.subscribers.name(name);
+    }
+
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Take};
+    /// let store = Take::new(&Observable::new(1), 2);
+    /// assert_eq!(store.subscriber_count(), 0);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count()
+    }
+
+    /// Subscribes to value changes on behalf of a weakly referenced owner.
+    ///
+    /// Only a weak reference to `owner` is kept, so the subscription does not keep it alive.
+    /// The callback is run immediately and on every change as long as the owner exists.
+    /// Once the owner is dropped, the subscription removes itself on the next change.
+    /// It returns a handle that can be called to unsubscribe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use stores::{Observable, Take};
+    /// let store = Take::new(&Observable::new(1), 2);
+    /// let owner = Arc::new(Mutex::new(0));
+    /// let unsubscribe = store.subscribe_weak(&owner, |owner, value| {
+    ///     *owner.lock().unwrap() = *value;
+    /// });
+    /// ```
+    pub fn subscribe_weak<Owner>(
+        &self,
+        owner: &Arc<Owner>,
+        callback: impl Fn(&Owner, &Value) + Send + Sync + 'static,
+    ) -> Unsubscribe
+    where
+        Owner: Send + Sync + 'static,
+    {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe_weak(value, owner, callback)
+    }
+
+    /// Internal function to stop listening to the source.
+    fn unsubscribe(&self) {
+        if let Some(unsubscribe) = self.source.write().take() {
+            unsubscribe.unsubscribe();
+        }
+    }
+
+    /// Internal function to run all registered callbacks.
+    fn notify(&self) {
+        let value = self.value.read().clone();
+        self.subscribers.notify(&value);
+    }
+}
+
+impl<Value> Emitter for Take<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.subscribers.listen(callback)
+    }
+}
+
+impl<Value> Readable<Value> for Take<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn get(&self) -> Value {
+        self.value.read().clone()
+    }
+
+    fn subscribe_boxed(&self, callback: Box<dyn Fn(&Value) + Send + Sync>) -> Unsubscribe {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe(value, callback)
+    }
+}
+
+impl<Value> ReadRef<Value> for Take<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    fn with<R>(&self, reader: impl FnOnce(&Value) -> R) -> R {
+        reader(&self.value.read())
+    }
+}
+
+impl<Value> Debug for Take<Value>
+where
+    Value: Debug + Clone + Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Take")
+            .field("value", &self.value.read())
+            .field("callbacks", &self.subscribers)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{Observable, Writable};
+
+    use super::*;
+
+    #[test]
+    fn it_takes_first_changes() {
+        let observable = Observable::new(0);
+        let taken = Take::new(&observable, 3);
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let _ = taken.on_change({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+
+        (1..=5).for_each(|value| observable.set(value));
+        assert_eq!(*values.lock().unwrap(), vec![1, 2, 3]);
+        assert_eq!(taken.get(), 3);
+        assert_eq!(observable.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_takes_nothing() {
+        let observable = Observable::new(0);
+        let taken = Take::new(&observable, 0);
+        assert_eq!(observable.subscriber_count(), 0);
+
+        observable.set(1);
+        assert_eq!(taken.get(), 0);
+    }
+}
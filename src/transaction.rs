@@ -0,0 +1,180 @@
+use std::{boxed::Box, cell::RefCell, vec::Vec};
+
+use crate::{batch::batch, depth};
+
+type Job = Box<dyn FnOnce()>;
+
+thread_local! {
+    /// Notifications deferred until the end of the current transaction by store,
+    /// `None` if no transaction is running.
+    static PENDING: RefCell<Option<Vec<(usize, Job)>>> = const { RefCell::new(None) };
+}
+
+/// Runs the given function as a transaction.
+///
+/// Observables changed inside the transaction notify their callbacks only once,
+/// with their latest value, after the function returns.
+/// The transaction is also run as a batch, so derived values that depend on several of the
+/// changed observables are only recomputed once.
+/// Transactions are tracked per thread and can be nested, in which case the outermost
+/// transaction decides when the notifications are sent.
+///
+/// # Example
+///
+/// ```
+/// use stores::{Observable, Readable, Writable, transaction};
+/// let a = Observable::new(1);
+/// let _ = a.on_change(|value| println!("{}", value));
+///
+/// transaction(|| {
+///     a.set(2);
+///     a.set(3);
+/// }); // prints 3 once
+/// assert_eq!(a.get(), 3);
+/// ```
+pub fn transaction<R>(func: impl FnOnce() -> R) -> R {
+    let outermost = PENDING.with_borrow_mut(|pending| {
+        if pending.is_some() {
+            return false;
+        }
+
+        *pending = Some(Vec::new());
+        true
+    });
+
+    if !outermost {
+        return func();
+    }
+
+    batch(|| {
+        let _guard = Guard;
+        let result = func();
+        loop {
+            let jobs = PENDING.with_borrow_mut(|pending| pending.as_mut().map(core::mem::take));
+            match jobs {
+                Some(jobs) if !jobs.is_empty() => jobs.into_iter().for_each(|(_, job)| job()),
+                _ => break,
+            }
+        }
+
+        result
+    })
+}
+
+/// Internal guard that ends the transaction, even if the function panics.
+struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        PENDING.with_borrow_mut(|pending| *pending = None);
+    }
+}
+
+/// Internal function to defer the notification of a store until the end of the transaction.
+///
+/// A later notification of the same store replaces the pending one.
+/// Runs the job immediately if no transaction is running, or if the store is already notifying,
+/// which queues the notification itself.
+pub(crate) fn defer(store: usize, job: impl FnOnce() + 'static) {
+    let job = PENDING.with_borrow_mut(|pending| match pending {
+        Some(pending) if !depth::is_notifying(store) => {
+            match pending.iter_mut().find(|(id, _)| *id == store) {
+                Some((_, pending)) => *pending = Box::new(job),
+                None => pending.push((store, Box::new(job))),
+            }
+            None
+        }
+        _ => Some(job),
+    });
+
+    if let Some(job) = job {
+        job();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        panic,
+        sync::{Arc, Mutex},
+    };
+
+    use crate::{Derived, Observable, Readable, Writable};
+
+    use super::*;
+
+    #[test]
+    fn it_replaces_pending_jobs_of_the_same_store() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        transaction(|| {
+            for (store, event) in [(1, "first"), (2, "second"), (1, "third")] {
+                let events = events.clone();
+                defer(store, move || events.lock().unwrap().push(event));
+            }
+            assert!(events.lock().unwrap().is_empty());
+        });
+
+        assert_eq!(*events.lock().unwrap(), vec!["third", "second"]);
+    }
+
+    #[test]
+    fn it_ends_the_transaction_on_panic() {
+        let result = panic::catch_unwind(|| transaction(|| panic!("failed")));
+        assert!(result.is_err());
+        assert!(PENDING.with_borrow(Option::is_none));
+    }
+
+    #[test]
+    fn it_notifies_once_per_transaction() {
+        let a = Observable::new(1);
+        let b = Observable::new(2);
+        let computations = Arc::new(Mutex::new(0));
+        let values = Arc::new(Mutex::new(Vec::new()));
+
+        let sum = Derived::new(&[a.clone(), b.clone()], {
+            let (a, b) = (a.clone(), b.clone());
+            let computations = computations.clone();
+            move || {
+                *computations.lock().unwrap() += 1;
+                a.get() + b.get()
+            }
+        });
+        let _ = a.on_change({
+            let values = values.clone();
+            move |value| values.lock().unwrap().push(*value)
+        });
+
+        transaction(|| {
+            a.set(10);
+            b.set(20);
+            a.set(30);
+            assert!(values.lock().unwrap().is_empty());
+        });
+
+        assert_eq!(sum.get(), 50);
+        assert_eq!(*computations.lock().unwrap(), 2);
+        assert_eq!(*values.lock().unwrap(), vec![30]);
+    }
+
+    #[test]
+    fn it_skips_callbacks_that_changed_their_observable_on_commit() {
+        let a = Observable::new(0);
+        let calls = Arc::new(Mutex::new(0));
+
+        let unsubscribe = a.on_change({
+            let a = a.clone();
+            let calls = calls.clone();
+            move |_| {
+                *calls.lock().unwrap() += 1;
+                a.set(5);
+            }
+        });
+
+        transaction(|| a.set(1));
+        assert_eq!(*calls.lock().unwrap(), 1);
+        assert_eq!(a.get(), 5);
+
+        unsubscribe();
+    }
+}
@@ -0,0 +1,266 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::fmt::Debug;
+
+use crate::{Emitter, ReadRef, Readable, Subscribers, Unsubscribe, lock::RwLock};
+
+type Compute<Value, Error> = Box<dyn Fn() -> Result<Value, Error> + Send + Sync>;
+
+/// A readable observable value that is derived from other observables by a fallible computation.
+///
+/// Holds the result of the last computation and separately keeps the last successful value.
+pub struct TryDerived<Value, Error>
+where
+    Value: Clone + Send + Sync,
+    Error: Clone + Send + Sync,
+{
+    value: RwLock<Result<Value, Error>>,
+    last_ok: RwLock<Option<Value>>,
+    compute: Compute<Value, Error>,
+    subscribers: Subscribers<Result<Value, Error>>,
+}
+
+impl<Value, Error> TryDerived<Value, Error>
+where
+    Value: Clone + Send + Sync + 'static,
+    Error: Clone + Send + Sync + 'static,
+{
+    /// Creates a new fallibly derived value.
+    ///
+    /// The result is recomputed whenever one of the targets changes.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable, TryDerived};
+    /// let input = Observable::new(String::from("1"));
+    /// let parsed = TryDerived::new(&[input.clone()], {
+    ///     let input = input.clone();
+    ///     move || input.get().parse::<i32>()
+    /// });
+    /// assert_eq!(parsed.get_ok(), Some(1));
+    /// ```
+    pub fn new(
+        targets: &[Arc<dyn Emitter + Send + Sync>],
+        compute: impl Fn() -> Result<Value, Error> + Send + Sync + 'static,
+    ) -> Arc<Self> {
+        let value = compute();
+
+        let instance = Arc::new(Self {
+            last_ok: RwLock::new(value.as_ref().ok().cloned()),
+            value: RwLock::new(value),
+            compute: Box::new(compute),
+            subscribers: Subscribers::new(),
+        });
+
+        for target in targets {
+            let unsubscribe = target.listen_boxed(Box::new({
+                let instance = Arc::downgrade(&instance);
+                move || {
+                    if let Some(instance) = instance.upgrade() {
+                        instance.refresh();
+                    }
+                }
+            }));
+            instance.subscribers.hold(unsubscribe);
+        }
+
+        instance
+    }
+
+    /// Names the store, so its changes are reported to the global hook.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Event, TryDerived};
+    /// let derived = TryDerived::<i32, ()>::new(&[Event::new()], || Ok(1));
+    /// derived.name("derived");
+    /// ```
+    #[cfg(feature = "debug")]
+    pub fn name(&self, name: &str)
+    where
+        Value: Debug,
+        Error: Debug,
+    {
+        self.subscribers.name(name);
+    }
+
+    /// Returns the current value if the last computation succeeded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Event, TryDerived};
+    /// let derived = TryDerived::<i32, ()>::new(&[Event::new()], || Ok(1));
+    /// assert_eq!(derived.get_ok(), Some(1));
+    /// ```
+    pub fn get_ok(&self) -> Option<Value> {
+        self.value.read().as_ref().ok().cloned()
+    }
+
+    /// Returns the error if the last computation failed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Event, TryDerived};
+    /// let derived = TryDerived::<i32, &str>::new(&[Event::new()], || Err("failed"));
+    /// assert_eq!(derived.get_err(), Some("failed"));
+    /// ```
+    pub fn get_err(&self) -> Option<Error> {
+        self.value.read().as_ref().err().cloned()
+    }
+
+    /// Returns the value of the last successful computation.
+    ///
+    /// Unlike `get_ok`, this keeps returning the previous value while the computation fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Event, TryDerived};
+    /// let derived = TryDerived::<i32, ()>::new(&[Event::new()], || Ok(1));
+    /// assert_eq!(derived.last_ok(), Some(1));
+    /// ```
+    pub fn last_ok(&self) -> Option<Value> {
+        self.last_ok.read().clone()
+    }
+
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Event, TryDerived};
+    /// let derived = TryDerived::<i32, ()>::new(&[Event::new()], || Ok(1));
+    /// assert_eq!(derived.subscriber_count(), 0);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count()
+    }
+
+    /// Internal function to recompute the result and run all registered callbacks.
+    fn refresh(&self) {
+        let value = (self.compute)();
+        if let Ok(value) = &value {
+            *self.last_ok.write() = Some(value.clone());
+        }
+
+        *self.value.write() = value;
+        self.notify();
+    }
+
+    /// Internal function to run all registered callbacks.
+    fn notify(&self) {
+        let value = self.value.read().clone();
+        self.subscribers.notify(&value);
+    }
+}
+
+impl<Value, Error> Emitter for TryDerived<Value, Error>
+where
+    Value: Clone + Send + Sync + 'static,
+    Error: Clone + Send + Sync + 'static,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.subscribers.listen(callback)
+    }
+}
+
+impl<Value, Error> Readable<Result<Value, Error>> for TryDerived<Value, Error>
+where
+    Value: Clone + Send + Sync + 'static,
+    Error: Clone + Send + Sync + 'static,
+{
+    fn get(&self) -> Result<Value, Error> {
+        self.value.read().clone()
+    }
+
+    fn subscribe_boxed(
+        &self,
+        callback: Box<dyn Fn(&Result<Value, Error>) + Send + Sync>,
+    ) -> Unsubscribe {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe(value, callback)
+    }
+}
+
+impl<Value, Error> ReadRef<Result<Value, Error>> for TryDerived<Value, Error>
+where
+    Value: Clone + Send + Sync,
+    Error: Clone + Send + Sync,
+{
+    fn with<R>(&self, reader: impl FnOnce(&Result<Value, Error>) -> R) -> R {
+        reader(&self.value.read())
+    }
+}
+
+impl<Value, Error> Debug for TryDerived<Value, Error>
+where
+    Value: Debug + Clone + Send + Sync,
+    Error: Debug + Clone + Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TryDerived")
+            .field("value", &self.value.read())
+            .field("last_ok", &self.last_ok.read())
+            .field("callbacks", &self.subscribers)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{num::ParseIntError, sync::Mutex};
+
+    use crate::{Observable, Writable, as_emitter};
+
+    use super::*;
+
+    #[test]
+    fn it_derives_fallibly() {
+        let input = Observable::new(String::from("1"));
+        let parsed = TryDerived::new(&[as_emitter(&input)], {
+            let input = input.clone();
+            move || input.get().parse::<i32>()
+        });
+
+        assert_eq!(parsed.get(), Ok(1));
+        assert_eq!(parsed.get_err(), None);
+
+        input.set(String::from("invalid"));
+        assert_eq!(parsed.get_ok(), None);
+        assert!(parsed.get_err().is_some());
+        assert_eq!(parsed.last_ok(), Some(1));
+
+        input.set(String::from("2"));
+        assert_eq!(parsed.get_ok(), Some(2));
+        assert_eq!(parsed.get_err(), None);
+        assert_eq!(parsed.last_ok(), Some(2));
+    }
+
+    #[test]
+    fn it_notifies_on_every_result() {
+        let input = Observable::new(String::from("1"));
+        let parsed = TryDerived::new(&[as_emitter(&input)], {
+            let input = input.clone();
+            move || input.get().parse::<i32>()
+        });
+        let results = Arc::new(Mutex::new(Vec::<Result<i32, ParseIntError>>::new()));
+
+        let _ = parsed.subscribe({
+            let results = results.clone();
+            move |result| results.lock().unwrap().push(result.clone())
+        });
+
+        input.set(String::from("invalid"));
+        input.set(String::from("3"));
+
+        let results = results.lock().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], Ok(1));
+        assert!(results[1].is_err());
+        assert_eq!(results[2], Ok(3));
+    }
+}
@@ -0,0 +1,317 @@
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    sync::{Arc, Weak},
+};
+use core::{
+    fmt::Debug,
+    ops::Deref,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::lock::RwLock;
+
+/// Handle that removes a callback when called.
+///
+/// Returned by `listen` and `subscribe`.
+/// It can be called like a function or consumed via `unsubscribe`,
+/// and can be freely stored and sent across threads.
+pub struct Unsubscribe(Box<dyn Fn() + Send + Sync>);
+
+impl Unsubscribe {
+    /// Creates a new handle that runs the given function to unsubscribe.
+    ///
+    /// Useful for implementing `Emitter` or `Readable` on custom types.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::Unsubscribe;
+    /// let unsubscribe = Unsubscribe::new(|| println!("unsubscribed"));
+    /// unsubscribe(); // "unsubscribed"
+    /// ```
+    pub fn new(func: impl Fn() + Send + Sync + 'static) -> Self {
+        Self(Box::new(func))
+    }
+
+    /// Removes the callback, consuming the handle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Event, Emitter};
+    /// let event = Event::new();
+    /// let unsubscribe = event.listen(|| {});
+    /// unsubscribe.unsubscribe();
+    /// assert_eq!(event.subscriber_count(), 0);
+    /// ```
+    pub fn unsubscribe(self) {
+        (self.0)();
+    }
+
+    /// Internal function to create a handle that removes the callback with the given id.
+    ///
+    /// Only keeps a weak reference, so the callbacks are not kept alive by the handle.
+    pub(crate) fn remove<Key, Callback>(
+        callbacks: &Arc<RwLock<BTreeMap<Key, Callback>>>,
+        id: Key,
+    ) -> Self
+    where
+        Key: Ord + Send + Sync + 'static,
+        Callback: Send + Sync + 'static,
+    {
+        let callbacks: Weak<_> = Arc::downgrade(callbacks);
+        Self::new(move || {
+            if let Some(callbacks) = callbacks.upgrade() {
+                callbacks.write().remove(&id);
+            }
+        })
+    }
+}
+
+/// Guard that unsubscribes when it is dropped.
+///
+/// Returned by `listen_guard` and `subscribe_guard`, or created from an `Unsubscribe` handle.
+#[must_use = "the callback is removed as soon as the subscription is dropped"]
+pub struct Subscription {
+    unsubscribe: Option<Unsubscribe>,
+}
+
+impl Subscription {
+    /// Keeps the callback registered forever, consuming the guard.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Event, Emitter};
+    /// let event = Event::new();
+    /// event.listen_guard(|| {}).forget();
+    /// assert_eq!(event.subscriber_count(), 1);
+    /// ```
+    pub fn forget(mut self) {
+        self.unsubscribe = None;
+    }
+}
+
+impl From<Unsubscribe> for Subscription {
+    fn from(unsubscribe: Unsubscribe) -> Self {
+        Self {
+            unsubscribe: Some(unsubscribe),
+        }
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(unsubscribe) = self.unsubscribe.take() {
+            unsubscribe.unsubscribe();
+        }
+    }
+}
+
+impl Debug for Subscription {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Subscription")
+            .field("active", &self.unsubscribe.is_some())
+            .finish()
+    }
+}
+
+/// Handle to a callback that can be paused and resumed.
+///
+/// Returned by `listen_pausable`. Like `Unsubscribe`, dropping the handle keeps the callback
+/// registered.
+pub struct PausableSubscription {
+    paused: Arc<AtomicBool>,
+    unsubscribe: Unsubscribe,
+}
+
+impl PausableSubscription {
+    /// Internal function to create a handle from the shared pause flag.
+    pub(crate) fn new(paused: Arc<AtomicBool>, unsubscribe: Unsubscribe) -> Self {
+        Self {
+            paused,
+            unsubscribe,
+        }
+    }
+
+    /// Skips the callback until `resume` is called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Emitter, Event};
+    /// let event = Event::new();
+    /// let subscription = event.listen_pausable(|| {});
+    /// subscription.pause();
+    /// assert!(subscription.is_paused());
+    /// ```
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Release);
+    }
+
+    /// Runs the callback again on every change.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Emitter, Event};
+    /// let event = Event::new();
+    /// let subscription = event.listen_pausable(|| {});
+    /// subscription.pause();
+    /// subscription.resume();
+    /// assert!(!subscription.is_paused());
+    /// ```
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::Release);
+    }
+
+    /// Returns whether the callback is paused.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Emitter, Event};
+    /// let event = Event::new();
+    /// let subscription = event.listen_pausable(|| {});
+    /// assert!(!subscription.is_paused());
+    /// ```
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Acquire)
+    }
+
+    /// Removes the callback, consuming the handle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Emitter, Event};
+    /// let event = Event::new();
+    /// let subscription = event.listen_pausable(|| {});
+    /// subscription.unsubscribe();
+    /// assert_eq!(event.subscriber_count(), 0);
+    /// ```
+    pub fn unsubscribe(self) {
+        self.unsubscribe.unsubscribe();
+    }
+}
+
+impl Debug for PausableSubscription {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PausableSubscription")
+            .field("paused", &self.is_paused())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Deref for Unsubscribe {
+    type Target = dyn Fn() + Send + Sync;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl Debug for Unsubscribe {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Unsubscribe").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Mutex, thread, vec::Vec};
+
+    use crate::{Emitter, Event, Observable, Readable, Writable};
+
+    use super::*;
+
+    #[test]
+    fn it_stores_handles_in_collections() {
+        let event = Event::new();
+        let observable = Observable::new(1);
+
+        let handles: Vec<Unsubscribe> = vec![
+            event.listen(|| {}),
+            observable.listen(|| {}),
+            observable.subscribe(|_| {}),
+        ];
+        assert_eq!(event.subscriber_count(), 1);
+        assert_eq!(observable.subscriber_count(), 2);
+
+        handles.iter().for_each(|unsubscribe| unsubscribe());
+        assert_eq!(event.subscriber_count(), 0);
+        assert_eq!(observable.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_unsubscribes_from_other_threads() {
+        let observable = Observable::new(1);
+        let counter = Arc::new(Mutex::new(0));
+
+        let unsubscribe = observable.subscribe({
+            let counter = counter.clone();
+            move |value| *counter.lock().unwrap() = *value
+        });
+
+        thread::spawn(move || unsubscribe.unsubscribe())
+            .join()
+            .unwrap();
+
+        observable.set(2);
+        assert_eq!(*counter.lock().unwrap(), 1);
+        assert_eq!(observable.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn it_unsubscribes_when_guard_is_dropped() {
+        let observable = Observable::new(1);
+        let counter = Arc::new(Mutex::new(0));
+
+        {
+            let _subscription = observable.subscribe_guard({
+                let counter = counter.clone();
+                move |value| *counter.lock().unwrap() = *value
+            });
+            assert_eq!(observable.subscriber_count(), 1);
+
+            observable.set(2);
+        }
+        assert_eq!(observable.subscriber_count(), 0);
+
+        observable.set(3);
+        assert_eq!(*counter.lock().unwrap(), 2);
+    }
+
+    #[test]
+    fn it_keeps_forgotten_subscriptions() {
+        let event = Event::new();
+
+        event.listen_guard(|| {}).forget();
+        assert_eq!(event.subscriber_count(), 1);
+    }
+
+    #[test]
+    fn it_pauses_and_resumes_callbacks() {
+        let event = Event::new();
+        let counter = Arc::new(Mutex::new(0));
+
+        let subscription = event.listen_pausable({
+            let counter = counter.clone();
+            move || *counter.lock().unwrap() += 1
+        });
+
+        subscription.pause();
+        event.dispatch();
+        assert_eq!(*counter.lock().unwrap(), 0);
+
+        subscription.resume();
+        event.dispatch();
+        assert_eq!(*counter.lock().unwrap(), 1);
+        assert_eq!(event.subscriber_count(), 1);
+
+        subscription.unsubscribe();
+        event.dispatch();
+        assert_eq!(*counter.lock().unwrap(), 1);
+    }
+}
@@ -13,7 +13,7 @@ macro_rules! derive {
     ([$($target:ident),*] => $func:expr) => {
 
         $crate::Derived::new(
-            &[$($target.clone()),*],
+            &[$($target.clone() as _),*],
             {
                 $( let $target = $target.clone(); )*
                 $func
@@ -23,6 +23,91 @@ macro_rules! derive {
     };
 }
 
+/// Simplifies the creation of derived values that only notify on actual changes
+///
+/// Like `derive!`, but the value must implement `PartialEq`.
+///
+/// # Example
+///
+/// ```
+/// use stores::{Observable, Readable, dedup};
+/// let a = Observable::new(1);
+/// let b = Observable::new(2);
+/// let positive = dedup!([a, b] => move || a.get() + b.get() > 0);
+/// ```
+#[macro_export]
+macro_rules! dedup {
+    ([$($target:ident),*] => $func:expr) => {
+
+        $crate::Derived::new_deduped(
+            &[$($target.clone() as _),*],
+            {
+                $( let $target = $target.clone(); )*
+                $func
+            }
+        )
+
+    };
+}
+
+/// Simplifies the creation of derived tuples
+///
+/// # Example
+///
+/// ```
+/// use stores::{Observable, Readable, combine};
+/// let a = Observable::new(1);
+/// let b = Observable::new("b");
+/// let both = combine!([a, b]);
+/// assert_eq!(both.get(), (1, "b"));
+/// ```
+#[macro_export]
+macro_rules! combine {
+    ([$($target:ident),*]) => {
+        $crate::derive!([$($target),*] => move || ($($crate::Readable::get(&*$target),)*))
+    };
+}
+
+/// Simplifies running side effects whenever any target changes.
+///
+/// The effect is run once immediately and then on every change.
+/// It returns a handle that can be called to unsubscribe from all targets.
+///
+/// # Example
+///
+/// ```
+/// use stores::{Observable, Readable, effect};
+/// let a = Observable::new(1);
+/// let b = Observable::new(2);
+/// let unsubscribe = effect!([a, b] => move || println!("{}", a.get() + b.get()));
+/// ```
+#[macro_export]
+macro_rules! effect {
+    ([$($target:ident),*] => $func:expr) => {{
+        let trigger = $crate::Event::new();
+        let _ = $crate::Emitter::listen(&*trigger, {
+            $( let $target = $target.clone(); )*
+            $func
+        });
+        trigger.dispatch();
+
+        let unsubscribe = || {};
+        $(
+            let unsubscribe = {
+                let current = $crate::Emitter::listen(&*$target, {
+                    let trigger = trigger.clone();
+                    move || trigger.dispatch()
+                });
+                move || {
+                    unsubscribe();
+                    current();
+                }
+            };
+        )*
+        $crate::Unsubscribe::new(unsubscribe)
+    }};
+}
+
 /// Simplifies cloning for callbacks.
 ///
 /// # Example
@@ -48,7 +133,9 @@ macro_rules! clone {
 #[cfg(test)]
 mod tests {
 
-    use crate::{Observable, Readable, Writable};
+    use std::sync::{Arc, Mutex};
+
+    use crate::{Emitter, Observable, Readable, Writable};
 
     #[test]
     fn it_derives() {
@@ -78,4 +165,56 @@ mod tests {
 
         assert_eq!(sum.get(), 7);
     }
+
+    #[test]
+    fn it_combines() {
+        let a = Observable::new(1);
+        let b = Observable::new(String::from("b"));
+        let c = Observable::new(true);
+
+        let combined = combine!([a, b, c]);
+        assert_eq!(combined.get(), (1, String::from("b"), true));
+
+        a.set(2);
+        c.set(false);
+        assert_eq!(combined.get(), (2, String::from("b"), false));
+    }
+
+    #[test]
+    fn it_runs_effects() {
+        let a = Observable::new(1);
+        let b = Observable::new(2);
+        let sums = Arc::new(Mutex::new(Vec::new()));
+
+        let unsubscribe = effect!([a, b] => clone!([sums] => move || {
+            sums.lock().unwrap().push(a.get() + b.get());
+        }));
+        assert_eq!(*sums.lock().unwrap(), vec![3]);
+
+        a.set(2);
+        b.set(3);
+        assert_eq!(*sums.lock().unwrap(), vec![3, 4, 5]);
+
+        unsubscribe();
+        a.set(10);
+        assert_eq!(*sums.lock().unwrap(), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn it_dedups() {
+        let a = Observable::new(1);
+        let b = Observable::new(2);
+        let notified = Arc::new(Mutex::new(0));
+
+        let positive = dedup!([a, b] => move || a.get() + b.get() > 0);
+        let _ = positive.listen(clone!([notified] => move || *notified.lock().unwrap() += 1));
+
+        a.set(5);
+        b.set(-1);
+        assert_eq!(*notified.lock().unwrap(), 0);
+
+        a.set(-5);
+        assert!(!positive.get());
+        assert_eq!(*notified.lock().unwrap(), 1);
+    }
 }
@@ -0,0 +1,105 @@
+use alloc::sync::{Arc, Weak};
+use core::fmt::Debug;
+
+use crate::Observable;
+
+/// A weak handle to an observable.
+///
+/// Does not keep the observable alive, which allows to break reference cycles between stores.
+/// It has to be upgraded to access the observable.
+pub struct WeakObservable<Value>
+where
+    Value: Send + Sync,
+{
+    target: Weak<Observable<Value>>,
+}
+
+impl<Value> WeakObservable<Value>
+where
+    Value: Send + Sync,
+{
+    /// Creates a new weak handle to the observable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, WeakObservable};
+    /// let observable = Observable::new(1);
+    /// let weak = WeakObservable::new(&observable);
+    /// ```
+    pub fn new(target: &Arc<Observable<Value>>) -> Self {
+        Self {
+            target: Arc::downgrade(target),
+        }
+    }
+
+    /// Returns the observable, if it is still alive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable};
+    /// let observable = Observable::new(1);
+    /// let weak = observable.downgrade();
+    /// assert_eq!(weak.upgrade().map(|observable| observable.get()), Some(1));
+    ///
+    /// drop(observable);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    pub fn upgrade(&self) -> Option<Arc<Observable<Value>>> {
+        self.target.upgrade()
+    }
+}
+
+impl<Value> Clone for WeakObservable<Value>
+where
+    Value: Send + Sync,
+{
+    fn clone(&self) -> Self {
+        Self {
+            target: self.target.clone(),
+        }
+    }
+}
+
+impl<Value> Debug for WeakObservable<Value>
+where
+    Value: Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("WeakObservable")
+            .field("alive", &(self.target.strong_count() > 0))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Readable, Writable};
+
+    use super::*;
+
+    #[test]
+    fn it_upgrades_while_alive() {
+        let observable = Observable::new(1);
+        let weak = observable.downgrade();
+
+        weak.upgrade().unwrap().set(2);
+        assert_eq!(observable.get(), 2);
+    }
+
+    #[test]
+    fn it_fails_to_upgrade_once_dropped() {
+        let observable = Observable::new(1);
+        let first = observable.clone();
+        let weak = WeakObservable::new(&observable);
+        let copy = weak.clone();
+
+        drop(observable);
+        assert!(weak.upgrade().is_some());
+
+        drop(first);
+        assert!(weak.upgrade().is_none());
+        assert!(copy.upgrade().is_none());
+    }
+}
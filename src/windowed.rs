@@ -0,0 +1,182 @@
+use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
+use core::fmt::Debug;
+
+use crate::{Emitter, ReadRef, Readable, Subscribers, Unsubscribe, lock::RwLock};
+
+/// A readable observable value that holds the latest values of another readable.
+///
+/// Useful for sparklines or moving averages.
+pub struct Windowed<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    value: RwLock<VecDeque<Value>>,
+    subscribers: Subscribers<VecDeque<Value>>,
+}
+
+impl<Value> Windowed<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    /// Creates a new window over the latest `capacity` values of the source.
+    ///
+    /// The current source value is added immediately, every following source value whenever the
+    /// source changes. Once the window is full, the oldest value is dropped for every new one.
+    /// The result is wrapped inside an Arc to be easily transferable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Readable, Windowed, Writable};
+    /// let observable = Observable::new(1);
+    /// let window = Windowed::new(&observable, 2);
+    ///
+    /// observable.set(2);
+    /// observable.set(3);
+    /// assert_eq!(window.get(), [2, 3]);
+    /// ```
+    pub fn new(
+        source: &Arc<impl Readable<Value> + Send + Sync + 'static>,
+        capacity: usize,
+    ) -> Arc<Self> {
+        assert!(capacity > 0, "the capacity of a window must not be zero");
+
+        let instance = Arc::new(Self {
+            value: RwLock::new(VecDeque::with_capacity(capacity)),
+            subscribers: Subscribers::new(),
+        });
+
+        let unsubscribe = source.subscribe({
+            let instance = Arc::downgrade(&instance);
+            move |value| {
+                if let Some(instance) = instance.upgrade() {
+                    {
+                        let mut window = instance.value.write();
+                        if window.len() == capacity {
+                            window.pop_front();
+                        }
+                        window.push_back(value.clone());
+                    }
+                    instance.notify();
+                }
+            }
+        });
+        instance.subscribers.hold(unsubscribe);
+        instance.subscribers.keep(source.clone());
+
+        instance
+    }
+
+    /// Names the store, so its changes are reported to the global hook.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Windowed};
+    /// let window = Windowed::new(&Observable::new(1), 10);
+    /// window.name("window");
+    /// ```
+    #[cfg(feature = "debug")]
+    pub fn name(&self, name: &str)
+    where
+        Value: Debug,
+    {
+        self.subscribers.name(name);
+    }
+
+    /// Returns the number of currently registered callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use stores::{Observable, Windowed};
+    /// let window = Windowed::new(&Observable::new(1), 10);
+    /// assert_eq!(window.subscriber_count(), 0);
+    /// ```
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.count()
+    }
+
+    /// Internal function to run all registered callbacks.
+    fn notify(&self) {
+        let value = self.value.read().clone();
+        self.subscribers.notify(&value);
+    }
+}
+
+impl<Value> Emitter for Windowed<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn listen_boxed(&self, callback: Box<dyn Fn() + Send + Sync>) -> Unsubscribe {
+        self.subscribers.listen(callback)
+    }
+}
+
+impl<Value> Readable<VecDeque<Value>> for Windowed<Value>
+where
+    Value: Clone + Send + Sync + 'static,
+{
+    fn get(&self) -> VecDeque<Value> {
+        self.value.read().clone()
+    }
+
+    fn subscribe_boxed(
+        &self,
+        callback: Box<dyn Fn(&VecDeque<Value>) + Send + Sync>,
+    ) -> Unsubscribe {
+        let value = self.value.read().clone();
+        self.subscribers.subscribe(value, callback)
+    }
+}
+
+impl<Value> ReadRef<VecDeque<Value>> for Windowed<Value>
+where
+    Value: Clone + Send + Sync,
+{
+    fn with<R>(&self, reader: impl FnOnce(&VecDeque<Value>) -> R) -> R {
+        reader(&self.value.read())
+    }
+}
+
+impl<Value> Debug for Windowed<Value>
+where
+    Value: Debug + Clone + Send + Sync,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Windowed")
+            .field("value", &self.value.read())
+            .field("callbacks", &self.subscribers)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Observable, Writable};
+
+    use super::*;
+
+    #[test]
+    fn it_slides_over_the_latest_values() {
+        let observable = Observable::new(1);
+        let window = Windowed::new(&observable, 3);
+        assert_eq!(window.get(), [1]);
+
+        for value in 2..=5 {
+            observable.set(value);
+            assert!(window.with(|window| window.len()) <= 3);
+        }
+        assert_eq!(window.get(), [3, 4, 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_rejects_empty_windows() {
+        Windowed::new(&Observable::new(1), 0);
+    }
+}